@@ -0,0 +1,563 @@
+use crate::config::{AppConfig, ControlHotkeys, Layer, MonitorConfig};
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyIssueCode {
+    DuplicateKey,
+    ControlKeyConflict,
+    NudgeKeyConflict,
+    DuplicateControlKey,
+    StageOverlap,
+    UnbindableKey,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyIssue {
+    pub code: KeyIssueCode,
+    pub message: String,
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layer: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stage: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyConflictReport {
+    // 会让按键无法触达的冲突，validate_config 直接拒绝
+    pub errors: Vec<KeyIssue>,
+    // 能用但容易看错或按错
+    pub warnings: Vec<KeyIssue>,
+}
+
+// 与前端 engine 的 normalizeKey / KEY_ALIASES 保持一致
+pub fn normalize_key(value: &str) -> String {
+    let lower = value.to_lowercase();
+    let alias = match lower.as_str() {
+        "escape" | "esc" => "esc",
+        "space" | "spacebar" | " " => "space",
+        "arrowleft" => "left",
+        "arrowright" => "right",
+        "arrowup" => "up",
+        "arrowdown" => "down",
+        _ => return lower,
+    };
+    alias.to_string()
+}
+
+pub const NUDGE_KEYS: [&str; 4] = ["left", "right", "up", "down"];
+
+struct Stage<'a> {
+    layer: usize,
+    stage: Option<usize>,
+    keys: Vec<&'a String>,
+    // 二分层自行处理方向键，不与微调冲突
+    allows_arrows: bool,
+    // 定点层按显示器分组，未指定显示器的点在各组中同样生效
+    monitor: Option<&'a str>,
+    shared: Vec<&'a String>,
+    // 不属于任何层的按键组（如落点锚点）用名称代替层号
+    name: Option<&'static str>,
+    // 另一套层列表（如光标附近模式的 local.layers）
+    scope: Option<String>,
+}
+
+// 兜底层排在所有层之后，序号为 layers.len()；锚点步骤再排在其后
+fn config_stages(config: &AppConfig) -> Vec<Stage<'_>> {
+    let layers = config.layers.iter().chain(&config.finish.fallback_layer);
+    let mut stages = layer_stages(layers, None);
+    if !config.finish.anchor_keys.is_empty() {
+        stages.push(Stage {
+            layer: config.layers.len() + usize::from(config.finish.fallback_layer.is_some()),
+            stage: None,
+            keys: config.finish.anchor_keys.iter().collect(),
+            allows_arrows: false,
+            monitor: None,
+            shared: Vec::new(),
+            name: Some("finish anchorKeys"),
+            scope: None,
+        });
+    }
+    stages.extend(layer_stages(
+        config.local.layers.iter(),
+        Some("local".to_string()),
+    ));
+    stages.extend(layer_stages(
+        config.window.layers.iter(),
+        Some("window".to_string()),
+    ));
+    for profile in &config.monitor.profiles {
+        let scope = format!("monitor profile {}", profile.monitor);
+        stages.extend(layer_stages(profile.layers.iter(), Some(scope)));
+    }
+    stages
+}
+
+fn layer_stages<'a>(
+    layers: impl Iterator<Item = &'a Layer>,
+    scope: Option<String>,
+) -> Vec<Stage<'a>> {
+    let mut stages = Vec::new();
+    for (layer_index, layer) in layers.enumerate() {
+        match layer {
+            Layer::Single { keys, .. } => stages.push(Stage {
+                layer: layer_index,
+                stage: None,
+                keys: keys.iter().collect(),
+                allows_arrows: false,
+                monitor: None,
+                shared: Vec::new(),
+                name: None,
+                scope: scope.clone(),
+            }),
+            Layer::Combo { stages: combo, .. } => {
+                for (stage_index, stage) in combo.iter().enumerate() {
+                    stages.push(Stage {
+                        layer: layer_index,
+                        stage: Some(stage_index),
+                        keys: stage.keys.iter().collect(),
+                        allows_arrows: false,
+                        monitor: None,
+                        shared: Vec::new(),
+                        name: None,
+                        scope: scope.clone(),
+                    });
+                }
+            }
+            Layer::Bisect {
+                left,
+                right,
+                up,
+                down,
+                finish,
+            } => stages.push(Stage {
+                layer: layer_index,
+                stage: None,
+                keys: vec![left, right, up, down, finish],
+                allows_arrows: true,
+                monitor: None,
+                shared: Vec::new(),
+                name: None,
+                scope: scope.clone(),
+            }),
+            Layer::Points { points } => {
+                let unscoped: Vec<&String> = points
+                    .iter()
+                    .filter(|point| point.monitor.is_none())
+                    .map(|point| &point.key)
+                    .collect();
+                let mut monitors: Vec<&str> = points
+                    .iter()
+                    .filter_map(|point| point.monitor.as_deref())
+                    .collect();
+                monitors.sort_unstable();
+                monitors.dedup();
+                stages.push(Stage {
+                    layer: layer_index,
+                    stage: None,
+                    keys: unscoped.clone(),
+                    allows_arrows: false,
+                    monitor: None,
+                    shared: Vec::new(),
+                    name: None,
+                    scope: scope.clone(),
+                });
+                for monitor in monitors {
+                    stages.push(Stage {
+                        layer: layer_index,
+                        stage: None,
+                        keys: points
+                            .iter()
+                            .filter(|point| point.monitor.as_deref() == Some(monitor))
+                            .map(|point| &point.key)
+                            .collect(),
+                        allows_arrows: false,
+                        monitor: Some(monitor),
+                        shared: unscoped.clone(),
+                        name: None,
+                        scope: scope.clone(),
+                    });
+                }
+            }
+        }
+    }
+    stages
+}
+
+pub fn control_keys(controls: &ControlHotkeys) -> [(&'static str, &String); 5] {
+    [
+        ("cancel", &controls.cancel),
+        ("undo", &controls.undo),
+        ("directClick", &controls.direct_click),
+        ("switchAction", &controls.switch_action),
+        ("nextMonitor", &controls.next_monitor),
+    ]
+}
+
+// 切换显示器的按键与控制键一样在遮罩期间全程有效
+fn monitor_keys(monitor: &MonitorConfig) -> Vec<(String, &String)> {
+    let directions = &monitor.direction_keys;
+    let mut keys = vec![
+        ("previousMonitor".to_string(), &monitor.previous_key),
+        ("monitorLeft".to_string(), &directions.left),
+        ("monitorRight".to_string(), &directions.right),
+        ("monitorUp".to_string(), &directions.up),
+        ("monitorDown".to_string(), &directions.down),
+    ];
+    for (index, key) in monitor.jump_keys.iter().enumerate() {
+        keys.push((format!("jumpMonitor{}", index), key));
+    }
+    keys
+}
+
+fn overlay_controls(config: &AppConfig) -> Vec<(String, &String)> {
+    control_keys(&config.hotkeys.controls)
+        .into_iter()
+        .map(|(name, key)| (name.to_string(), key))
+        .chain(monitor_keys(&config.monitor))
+        .collect()
+}
+
+// 层不能使用的按键（已规范化）：控制键、切换显示器和微调
+pub fn reserved_keys(config: &AppConfig) -> Vec<String> {
+    overlay_controls(config)
+        .into_iter()
+        .filter(|(_, key)| !key.trim().is_empty())
+        .map(|(_, key)| normalize_key(key))
+        .chain(NUDGE_KEYS.iter().map(|key| key.to_string()))
+        .collect()
+}
+
+fn stage_label(stage: &Stage) -> String {
+    if let Some(name) = stage.name {
+        return name.to_string();
+    }
+    let layer = match &stage.scope {
+        Some(scope) => format!("{} layer {}", scope, stage.layer),
+        None => format!("layer {}", stage.layer),
+    };
+    match (stage.stage, stage.monitor) {
+        (Some(index), _) => format!("{} stage{}", layer, index),
+        (None, Some(monitor)) => format!("{} on monitor {}", layer, monitor),
+        (None, None) => layer,
+    }
+}
+
+// is_bindable 判断按键能否注册为全局快捷键
+pub fn analyze_keys(config: &AppConfig, is_bindable: &dyn Fn(&str) -> bool) -> KeyConflictReport {
+    let mut report = KeyConflictReport::default();
+    let mut seen_controls: HashMap<String, String> = HashMap::new();
+    for (name, key) in overlay_controls(config) {
+        if key.trim().is_empty() {
+            continue;
+        }
+        let normalized = normalize_key(key);
+        if let Some(previous) = seen_controls.get(&normalized) {
+            report.errors.push(KeyIssue {
+                code: KeyIssueCode::DuplicateControlKey,
+                message: format!("controls {} and {} share key {}", previous, name, key),
+                key: key.to_string(),
+                layer: None,
+                stage: None,
+            });
+        } else {
+            seen_controls.insert(normalized, name);
+        }
+    }
+
+    let stages = config_stages(config);
+    for stage in &stages {
+        let label = stage_label(stage);
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for (index, key) in stage.shared.iter().enumerate() {
+            seen.entry(normalize_key(key)).or_insert(index);
+        }
+        let offset = stage.shared.len();
+        for (index, key) in stage.keys.iter().enumerate() {
+            let index = index + offset;
+            if key.trim().is_empty() {
+                continue;
+            }
+            let normalized = normalize_key(key);
+            let issue = |code, message| KeyIssue {
+                code,
+                message,
+                key: key.to_string(),
+                // layer 只指向 config.layers 中的序号
+                layer: (stage.name.is_none() && stage.scope.is_none()).then_some(stage.layer),
+                stage: stage.stage,
+            };
+
+            if let Some(first) = seen.get(&normalized) {
+                report.errors.push(issue(
+                    KeyIssueCode::DuplicateKey,
+                    format!(
+                        "{} lists key {} twice (cells {} and {})",
+                        label, key, first, index
+                    ),
+                ));
+            } else {
+                seen.insert(normalized.clone(), index);
+            }
+
+            if let Some(control) = seen_controls.get(&normalized) {
+                report.errors.push(issue(
+                    KeyIssueCode::ControlKeyConflict,
+                    format!("{} key {} is the {} control key", label, key, control),
+                ));
+            } else if !stage.allows_arrows && NUDGE_KEYS.contains(&normalized.as_str()) {
+                report.errors.push(issue(
+                    KeyIssueCode::NudgeKeyConflict,
+                    format!("{} key {} is reserved for nudging", label, key),
+                ));
+            } else if !is_bindable(key) {
+                report.warnings.push(issue(
+                    KeyIssueCode::UnbindableKey,
+                    format!(
+                        "{} key {} cannot be registered as a shortcut and will be ignored",
+                        label, key
+                    ),
+                ));
+            }
+        }
+    }
+
+    // 只有组合层的各段会连续按下，其余分组互不影响
+    for (index, later) in stages.iter().enumerate() {
+        if later.stage.is_none() {
+            continue;
+        }
+        for key in &later.keys {
+            let normalized = normalize_key(key);
+            let earlier = stages[..index].iter().find(|earlier| {
+                earlier.layer == later.layer
+                    && earlier.scope == later.scope
+                    && earlier.stage.is_some()
+                    && earlier
+                        .keys
+                        .iter()
+                        .any(|other| normalize_key(other) == normalized)
+            });
+            if let Some(earlier) = earlier {
+                report.warnings.push(KeyIssue {
+                    code: KeyIssueCode::StageOverlap,
+                    message: format!(
+                        "{}layer {} uses key {} in both stage{} and stage{}; chord labels become ambiguous",
+                        later.scope.as_ref().map(|scope| format!("{} ", scope)).unwrap_or_default(),
+                        later.layer,
+                        key,
+                        earlier.stage.unwrap_or_default(),
+                        later.stage.unwrap_or_default()
+                    ),
+                    key: key.to_string(),
+                    layer: later.scope.is_none().then_some(later.layer),
+                    stage: later.stage,
+                });
+            }
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{default_config, GridStage, HintPoint, MonitorProfile, PointOrigin};
+
+    fn bindable(_: &str) -> bool {
+        true
+    }
+
+    fn keys(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn default_config_is_clean() {
+        let report = analyze_keys(&default_config(), &bindable);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+    }
+
+    #[test]
+    fn reports_duplicates_and_control_conflicts() {
+        let mut config = default_config();
+        config.layers = vec![Layer::Single {
+            rows: 1,
+            cols: 4,
+            keys: keys(&["a", "A", "space", "ArrowLeft"]),
+            row_weights: None,
+            col_weights: None,
+            overlap_ratio: None,
+        }];
+
+        let report = analyze_keys(&config, &bindable);
+        let codes: Vec<KeyIssueCode> = report.errors.iter().map(|issue| issue.code).collect();
+        assert_eq!(
+            codes,
+            vec![
+                KeyIssueCode::DuplicateKey,
+                KeyIssueCode::ControlKeyConflict,
+                KeyIssueCode::NudgeKeyConflict,
+            ]
+        );
+        assert_eq!(report.errors[1].layer, Some(0));
+    }
+
+    #[test]
+    fn bisect_layers_may_use_arrows() {
+        let mut config = default_config();
+        config.layers = vec![Layer::Bisect {
+            left: "ArrowLeft".to_string(),
+            right: "ArrowRight".to_string(),
+            up: "ArrowUp".to_string(),
+            down: "Left".to_string(),
+            finish: "Space".to_string(),
+        }];
+
+        let report = analyze_keys(&config, &bindable);
+        let codes: Vec<KeyIssueCode> = report.errors.iter().map(|issue| issue.code).collect();
+        assert_eq!(
+            codes,
+            vec![KeyIssueCode::DuplicateKey, KeyIssueCode::ControlKeyConflict]
+        );
+    }
+
+    #[test]
+    fn reports_duplicate_control_keys() {
+        let mut config = default_config();
+        config.hotkeys.controls.undo = "Escape".to_string();
+        let report = analyze_keys(&config, &bindable);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].code, KeyIssueCode::DuplicateControlKey);
+    }
+
+    #[test]
+    fn warns_on_stage_overlap_and_unbindable_keys() {
+        let mut config = default_config();
+        config.layers = vec![Layer::Combo {
+            stages: vec![
+                GridStage::new(1, 2, keys(&["q", "w"])),
+                GridStage::new(2, 1, keys(&["w", "§"])),
+                GridStage::new(1, 2, keys(&["e", "Q"])),
+            ],
+            overlap_ratio: None,
+        }];
+
+        let report = analyze_keys(&config, &|key: &str| key != "§");
+        assert!(report.errors.is_empty());
+        let codes: Vec<KeyIssueCode> = report.warnings.iter().map(|issue| issue.code).collect();
+        assert_eq!(
+            codes,
+            vec![
+                KeyIssueCode::UnbindableKey,
+                KeyIssueCode::StageOverlap,
+                KeyIssueCode::StageOverlap,
+            ]
+        );
+        assert_eq!(report.warnings[1].stage, Some(1));
+        assert_eq!(report.warnings[2].stage, Some(2));
+    }
+
+    fn point(key: &str, monitor: Option<&str>) -> HintPoint {
+        HintPoint {
+            key: key.to_string(),
+            name: None,
+            x: 0.0,
+            y: 0.0,
+            origin: PointOrigin::Monitor,
+            monitor: monitor.map(str::to_string),
+            group: None,
+        }
+    }
+
+    #[test]
+    fn points_may_reuse_keys_across_monitors() {
+        let mut config = default_config();
+        config.layers = vec![Layer::Points {
+            points: vec![
+                point("t", None),
+                point("b", Some("DP-1")),
+                point("b", Some("HDMI-1")),
+                point("T", Some("HDMI-1")),
+            ],
+        }];
+
+        let report = analyze_keys(&config, &bindable);
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+        assert_eq!(report.errors.len(), 1, "{:?}", report.errors);
+        assert_eq!(report.errors[0].code, KeyIssueCode::DuplicateKey);
+        assert!(report.errors[0].message.contains("HDMI-1"));
+    }
+
+    #[test]
+    fn anchor_keys_are_checked_against_controls() {
+        let mut config = default_config();
+        config.finish.anchor_keys = keys(&["q", "w", "e", "a", "Space", "d", "z", "x", "c"]);
+
+        let report = analyze_keys(&config, &bindable);
+        assert_eq!(report.errors.len(), 1, "{:?}", report.errors);
+        assert_eq!(report.errors[0].code, KeyIssueCode::ControlKeyConflict);
+        assert_eq!(report.errors[0].layer, None);
+        assert!(report.errors[0].message.starts_with("finish anchorKeys"));
+    }
+
+    #[test]
+    fn monitor_keys_count_as_controls() {
+        let mut config = default_config();
+        config.monitor.jump_keys = keys(&["F1", "F2", "Esc"]);
+        config.monitor.direction_keys.left = "a".to_string();
+
+        let report = analyze_keys(&config, &bindable);
+        assert_eq!(report.errors[0].code, KeyIssueCode::DuplicateControlKey);
+        assert!(report.errors[0].message.contains("jumpMonitor2"));
+        // 主层、本地层里的 a 都会与切换键冲突
+        assert!(report.errors[1..]
+            .iter()
+            .all(|issue| issue.code == KeyIssueCode::ControlKeyConflict));
+        assert!(report.errors[1].message.contains("monitorLeft"));
+    }
+
+    #[test]
+    fn checks_local_layers_separately() {
+        let mut config = default_config();
+        // 与主层键位相同不算冲突，各自只在对应模式下生效
+        config.local.layers = config.layers.clone();
+        assert!(analyze_keys(&config, &bindable).errors.is_empty());
+
+        config.local.layers = vec![Layer::Single {
+            rows: 1,
+            cols: 2,
+            keys: keys(&["a", "Esc"]),
+            row_weights: None,
+            col_weights: None,
+            overlap_ratio: None,
+        }];
+        let report = analyze_keys(&config, &bindable);
+        assert_eq!(report.errors.len(), 1, "{:?}", report.errors);
+        assert_eq!(report.errors[0].layer, None);
+        assert!(report.errors[0].message.starts_with("local layer 0"));
+
+        config.window.layers = config.local.layers.clone();
+        let report = analyze_keys(&config, &bindable);
+        assert_eq!(report.errors.len(), 2, "{:?}", report.errors);
+        assert!(report.errors[1].message.starts_with("window layer 0"));
+
+        config.window.layers.clear();
+        config.monitor.profiles = vec![MonitorProfile {
+            monitor: "eDP-1".to_string(),
+            layers: config.local.layers.clone(),
+            ..MonitorProfile::default()
+        }];
+        let report = analyze_keys(&config, &bindable);
+        assert_eq!(report.errors.len(), 2, "{:?}", report.errors);
+        assert!(report.errors[1]
+            .message
+            .starts_with("monitor profile eDP-1 layer 0"));
+    }
+}
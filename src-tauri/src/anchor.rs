@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+
+// 点击落在最后一格中的位置，按小键盘顺序排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AnchorPosition {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Start,
+    Middle,
+    End,
+}
+
+impl AnchorPosition {
+    // (水平, 垂直)
+    fn sides(self) -> (Side, Side) {
+        match self {
+            Self::TopLeft => (Side::Start, Side::Start),
+            Self::Top => (Side::Middle, Side::Start),
+            Self::TopRight => (Side::End, Side::Start),
+            Self::Left => (Side::Start, Side::Middle),
+            Self::Center => (Side::Middle, Side::Middle),
+            Self::Right => (Side::End, Side::Middle),
+            Self::BottomLeft => (Side::Start, Side::End),
+            Self::Bottom => (Side::Middle, Side::End),
+            Self::BottomRight => (Side::End, Side::End),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnchorCell {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClickAnchor {
+    pub position: AnchorPosition,
+    pub cell: AnchorCell,
+}
+
+// 各轴上围绕 base 的落点范围，含两端
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LandingBounds {
+    pub min_x: i32,
+    pub max_x: i32,
+    pub min_y: i32,
+    pub max_y: i32,
+}
+
+// 边和角的锚点只向格子内侧随机偏移，所有锚点的落点都不出格子
+pub fn landing_bounds(
+    anchor: Option<&ClickAnchor>,
+    base: (i32, i32),
+    radius: i32,
+) -> LandingBounds {
+    let radius = radius.max(0);
+    let Some(anchor) = anchor else {
+        return LandingBounds {
+            min_x: base.0.saturating_sub(radius),
+            max_x: base.0.saturating_add(radius),
+            min_y: base.1.saturating_sub(radius),
+            max_y: base.1.saturating_add(radius),
+        };
+    };
+
+    let (horizontal, vertical) = anchor.position.sides();
+    let cell = &anchor.cell;
+    let (min_x, max_x) = axis_bounds(base.0, radius, horizontal, cell.x, cell.width);
+    let (min_y, max_y) = axis_bounds(base.1, radius, vertical, cell.y, cell.height);
+    LandingBounds {
+        min_x,
+        max_x,
+        min_y,
+        max_y,
+    }
+}
+
+fn axis_bounds(base: i32, radius: i32, side: Side, start: f64, length: f64) -> (i32, i32) {
+    let (low, high) = match side {
+        Side::Start => (base, base.saturating_add(radius)),
+        Side::Middle => (base.saturating_sub(radius), base.saturating_add(radius)),
+        Side::End => (base.saturating_sub(radius), base),
+    };
+    // 格子覆盖的像素为 [ceil(start), ceil(start + length) - 1]
+    let first = start.ceil() as i32;
+    let last = ((start + length).ceil() as i32 - 1).max(first);
+    let low = low.max(first);
+    let high = high.min(last);
+    if low > high || base < low || base > high {
+        // 落点本身不在格子内时不做随机化
+        (base, base)
+    } else {
+        (low, high)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anchor(position: AnchorPosition) -> ClickAnchor {
+        ClickAnchor {
+            position,
+            cell: AnchorCell {
+                x: 100.0,
+                y: 200.0,
+                width: 20.0,
+                height: 10.0,
+            },
+        }
+    }
+
+    #[test]
+    fn without_anchor_uses_full_square() {
+        let bounds = landing_bounds(None, (10, 20), 3);
+        assert_eq!(
+            bounds,
+            LandingBounds {
+                min_x: 7,
+                max_x: 13,
+                min_y: 17,
+                max_y: 23,
+            }
+        );
+    }
+
+    #[test]
+    fn corners_randomize_inwards_only() {
+        let bounds = landing_bounds(Some(&anchor(AnchorPosition::TopLeft)), (100, 200), 3);
+        assert_eq!((bounds.min_x, bounds.max_x), (100, 103));
+        assert_eq!((bounds.min_y, bounds.max_y), (200, 203));
+
+        let bounds = landing_bounds(Some(&anchor(AnchorPosition::BottomRight)), (119, 209), 3);
+        assert_eq!((bounds.min_x, bounds.max_x), (116, 119));
+        assert_eq!((bounds.min_y, bounds.max_y), (206, 209));
+    }
+
+    #[test]
+    fn center_stays_inside_small_cell() {
+        let bounds = landing_bounds(Some(&anchor(AnchorPosition::Center)), (110, 205), 8);
+        assert_eq!((bounds.min_x, bounds.max_x), (102, 118));
+        assert_eq!((bounds.min_y, bounds.max_y), (200, 209));
+
+        let bounds = landing_bounds(Some(&anchor(AnchorPosition::Top)), (110, 200), 2);
+        assert_eq!((bounds.min_x, bounds.max_x), (108, 112));
+        assert_eq!((bounds.min_y, bounds.max_y), (200, 202));
+    }
+
+    #[test]
+    fn base_outside_cell_is_not_randomized() {
+        let bounds = landing_bounds(Some(&anchor(AnchorPosition::Left)), (90, 205), 4);
+        assert_eq!((bounds.min_x, bounds.max_x), (90, 90));
+        assert_eq!((bounds.min_y, bounds.max_y), (201, 209));
+    }
+}
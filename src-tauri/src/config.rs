@@ -1,18 +1,29 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 // 与前端共享的默认配置（单一事实来源）
 pub const DEFAULT_CONFIG_JSON: &str = include_str!("../../src/lib/shared/default-config.json");
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AppConfig {
     pub app: AppSection,
     pub hotkeys: HotkeysConfig,
     #[serde(default)]
+    pub monitor: MonitorConfig,
+    #[serde(default)]
     pub nudge: NudgeConfig,
     #[serde(default)]
+    pub finish: FinishConfig,
+    #[serde(default)]
     pub mouse: MouseConfig,
+    #[schemars(length(min = 1))]
     pub layers: Vec<Layer>,
+    #[serde(default)]
+    pub local: LocalConfig,
+    #[serde(default)]
+    pub window: WindowModeConfig,
     pub overlay: OverlayConfig,
 }
 
@@ -22,7 +33,45 @@ impl Default for AppConfig {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl AppConfig {
+    /// The config the overlay runs with for a cursor-relative activation.
+    pub fn for_local(&self) -> AppConfig {
+        let mut config = self.clone();
+        config.layers = self.local.layers.clone();
+        config
+    }
+
+    /// The config the overlay runs with for a focused-window activation.
+    pub fn for_window(&self) -> AppConfig {
+        let mut config = self.clone();
+        if !self.window.layers.is_empty() {
+            config.layers = self.window.layers.clone();
+        }
+        config
+    }
+
+    /// The config for one monitor with its profile applied.
+    ///
+    /// Sizes stay in CSS pixels; the overlay applies `overlay.scaleWithMonitor` when drawing.
+    /// Apply before `for_local`/`for_window` so their layers still take precedence.
+    pub fn for_monitor(&self, name: Option<&str>, width: u32, height: u32) -> AppConfig {
+        let mut config = self.clone();
+        if let Some(profile) = self.monitor.profile_for(name, width, height) {
+            if !profile.layers.is_empty() {
+                config.layers = profile.layers.clone();
+            }
+            if let Some(size) = profile.font_size_px {
+                config.overlay.font.size_px = size;
+            }
+            if let Some(line_width) = profile.line_width_px {
+                config.overlay.line_width_px = line_width;
+            }
+        }
+        config
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AppSection {
     #[serde(default = "default_locale")]
@@ -31,44 +80,174 @@ pub struct AppSection {
     pub settings_window: SettingsWindowConfig,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TrayConfig {
     pub enabled: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SettingsWindowConfig {
     pub open_from_tray: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct HotkeysConfig {
     pub activation: ActivationHotkeys,
     pub controls: ControlHotkeys,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ActivationHotkeys {
+    #[schemars(length(min = 1))]
     pub trigger: String,
+    /// Opens a small grid around the cursor using `local.layers`; empty disables.
+    #[serde(default)]
+    pub local: String,
+    /// Opens the grid over the focused window; empty disables.
+    #[serde(default)]
+    pub window: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ControlHotkeys {
+    #[schemars(length(min = 1))]
     pub cancel: String,
+    #[schemars(length(min = 1))]
     pub undo: String,
+    #[schemars(length(min = 1))]
     pub direct_click: String,
+    #[schemars(length(min = 1))]
     pub switch_action: String,
+    #[schemars(length(min = 1))]
     pub next_monitor: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum StartStrategy {
+    #[default]
+    Primary,
+    /// The monitor under the mouse cursor.
+    Cursor,
+    /// The monitor showing most of the foreground window.
+    FocusedWindow,
+    /// The monitor the overlay was last shown on.
+    LastUsed,
+    /// The monitor whose name matches `startName`.
+    Named,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorConfig {
+    /// Which monitor the overlay opens on; falls back to the primary monitor.
+    #[serde(default)]
+    pub start_strategy: StartStrategy,
+    #[serde(default)]
+    pub start_name: String,
+    /// Cycles backwards through monitors; empty disables.
+    #[serde(default)]
+    pub previous_key: String,
+    /// `jumpKeys[i]` jumps straight to monitor `i`.
+    #[serde(default)]
+    pub jump_keys: Vec<String>,
+    /// Moves to the neighbouring monitor in that direction; empty keys are unbound.
+    #[serde(default)]
+    pub direction_keys: MonitorDirectionKeys,
+    /// With several monitors, start with a step that labels each one with its jump key.
+    #[serde(default)]
+    pub picker: bool,
+    /// With several monitors, show a grid on each at once; its jump key picks the monitor.
+    #[serde(default)]
+    pub show_all: bool,
+    /// With several monitors, the first layer covers the bounding box of all of them.
+    #[serde(default)]
+    pub span_all: bool,
+    /// Leaves out taskbars and panels by using each monitor's work area.
+    #[serde(default)]
+    pub use_work_area: bool,
+    /// Extra edges to leave out; an entry without `monitor` applies to every monitor.
+    #[serde(default)]
+    pub margins: Vec<MonitorMargins>,
+    /// Per-monitor layers and overlay sizes; the first match by name wins over resolution.
+    #[serde(default)]
+    pub profiles: Vec<MonitorProfile>,
+}
+
+impl MonitorConfig {
+    /// The profile keyed by this monitor's name, else by its `WIDTHxHEIGHT` resolution.
+    pub fn profile_for(
+        &self,
+        name: Option<&str>,
+        width: u32,
+        height: u32,
+    ) -> Option<&MonitorProfile> {
+        let resolution = format!("{}x{}", width, height);
+        self.profiles
+            .iter()
+            .find(|profile| name == Some(profile.monitor.as_str()))
+            .or_else(|| {
+                self.profiles
+                    .iter()
+                    .find(|profile| profile.monitor.eq_ignore_ascii_case(&resolution))
+            })
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorProfile {
+    /// Monitor name, or a resolution in physical pixels such as `3440x1440`.
+    #[schemars(length(min = 1))]
+    pub monitor: String,
+    /// Replaces `layers` on this monitor; empty keeps them.
+    #[serde(default)]
+    pub layers: Vec<Layer>,
+    /// Replaces `overlay.font.sizePx`; CSS pixels, scaled like the global size.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_size_px: Option<u32>,
+    /// Replaces `overlay.lineWidthPx`; CSS pixels, scaled like the global width.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_width_px: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorMargins {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monitor: Option<String>,
+    #[serde(default)]
+    pub top: u32,
+    #[serde(default)]
+    pub right: u32,
+    #[serde(default)]
+    pub bottom: u32,
+    #[serde(default)]
+    pub left: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorDirectionKeys {
+    #[serde(default)]
+    pub left: String,
+    #[serde(default)]
+    pub right: String,
+    #[serde(default)]
+    pub up: String,
+    #[serde(default)]
+    pub down: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NudgeConfig {
+    #[schemars(range(min = 1))]
     pub step_px: u32,
 }
 
@@ -78,46 +257,117 @@ impl Default for NudgeConfig {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Cursor-relative activation: a box centered on the mouse, clamped to its monitor.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalConfig {
+    #[schemars(range(min = 1))]
+    pub width_px: u32,
+    #[schemars(range(min = 1))]
+    pub height_px: u32,
+    #[schemars(length(min = 1))]
+    pub layers: Vec<Layer>,
+}
+
+impl Default for LocalConfig {
+    fn default() -> Self {
+        default_config().local
+    }
+}
+
+/// Focused-window activation: the first layer starts from the window bounds.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowModeConfig {
+    /// Layers for this mode; empty reuses `layers`.
+    #[serde(default)]
+    pub layers: Vec<Layer>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum AutoFinishAction {
+    /// Click the center of the region right away.
+    #[default]
+    Click,
+    /// Stop reading layer keys; arrows nudge and `directClick` clicks.
+    Nudge,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FinishConfig {
+    /// Finish once both sides of the region are below this many physical px; 0 disables.
+    #[serde(default)]
+    pub auto_below_px: u32,
+    #[serde(default)]
+    pub auto_action: AutoFinishAction,
+    /// Run `fallbackLayer` when the last layer leaves a side above this many physical px; 0 disables.
+    #[serde(default)]
+    pub fallback_above_px: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_layer: Option<Layer>,
+    /// Keypad-ordered 3x3 keys that pick where in the final cell to click; empty disables.
+    #[serde(default)]
+    pub anchor_keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct MouseConfig {
     #[serde(default = "default_mouse_smooth_move")]
     pub smooth_move: bool,
     #[serde(default = "default_mouse_move_duration_ms")]
+    #[schemars(range(min = 1))]
     pub move_duration_ms: u32,
     #[serde(default = "default_mouse_move_step_ms")]
+    #[schemars(range(min = 1))]
     pub move_step_ms: u32,
     #[serde(default = "default_mouse_press_duration_ms")]
     pub press_duration_ms: u32,
     #[serde(default = "default_mouse_landing_radius_px")]
     pub landing_radius_px: u32,
     #[serde(default = "default_mouse_duration_randomness")]
+    #[schemars(range(min = 0.0), extend("exclusiveMaximum" = 1.0))]
     pub duration_randomness: f64,
     #[serde(default = "default_mouse_step_randomness")]
+    #[schemars(range(min = 0.0), extend("exclusiveMaximum" = 1.0))]
     pub step_randomness: f64,
     #[serde(default = "default_mouse_distance_boost_px")]
+    #[schemars(extend("exclusiveMinimum" = 0.0))]
     pub distance_boost_px: f64,
     #[serde(default = "default_mouse_duration_distance_boost")]
+    #[schemars(range(min = 0.0), extend("exclusiveMaximum" = 1.0))]
     pub duration_distance_boost: f64,
     #[serde(default = "default_mouse_step_distance_boost")]
+    #[schemars(range(min = 0.0), extend("exclusiveMaximum" = 1.0))]
     pub step_distance_boost: f64,
     #[serde(default = "default_mouse_curve_along_ratio")]
+    #[schemars(range(min = 0.0, max = 1.0))]
     pub curve_along_ratio: f64,
     #[serde(default = "default_mouse_curve_spread_ratio")]
+    #[schemars(range(min = 0.0, max = 1.0))]
     pub curve_spread_ratio: f64,
     #[serde(default = "default_mouse_jitter_ratio")]
+    #[schemars(range(min = 0.0, max = 0.2))]
     pub jitter_ratio: f64,
     #[serde(default = "default_mouse_adaptive_stride_base_px")]
+    #[schemars(extend("exclusiveMinimum" = 0.0))]
     pub adaptive_stride_base_px: f64,
     #[serde(default = "default_mouse_adaptive_stride_distance_ratio")]
+    #[schemars(range(min = 0.0))]
     pub adaptive_stride_distance_ratio: f64,
     #[serde(default = "default_mouse_adaptive_stride_max_px")]
+    /// Must be >= `adaptiveStrideBasePx`.
+    #[schemars(extend("exclusiveMinimum" = 0.0))]
     pub adaptive_stride_max_px: f64,
     #[serde(default = "default_mouse_extra_steps_max")]
     pub extra_steps_max: u32,
     #[serde(default = "default_mouse_max_steps")]
+    #[schemars(range(min = 2))]
     pub max_steps: u32,
     #[serde(default = "default_mouse_max_step_sleep_ms")]
+    #[schemars(range(min = 1))]
     pub max_step_sleep_ms: u32,
 }
 
@@ -223,47 +473,266 @@ fn default_mouse_max_step_sleep_ms() -> u32 {
     24
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "mode", rename_all = "camelCase")]
+// 格子向四周扩展的比例上限，超过后相邻格子会互相覆盖过半
+pub const MAX_OVERLAP_RATIO: f64 = 0.5;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "mode", rename_all = "camelCase", try_from = "LayerDef")]
 pub enum Layer {
     Single {
         rows: u32,
         cols: u32,
         keys: Vec<String>,
+        #[serde(rename = "rowWeights", skip_serializing_if = "Option::is_none")]
+        row_weights: Option<Vec<f64>>,
+        #[serde(rename = "colWeights", skip_serializing_if = "Option::is_none")]
+        col_weights: Option<Vec<f64>>,
+        #[serde(rename = "overlapRatio", skip_serializing_if = "Option::is_none")]
+        overlap_ratio: Option<f64>,
+    },
+    /// Each stage narrows the region further; one key per stage.
+    Combo {
+        stages: Vec<GridStage>,
+        #[serde(rename = "overlapRatio", skip_serializing_if = "Option::is_none")]
+        overlap_ratio: Option<f64>,
+    },
+    /// Each direction key keeps one half of the region; `finish` ends the layer.
+    Bisect {
+        left: String,
+        right: String,
+        up: String,
+        down: String,
+        finish: String,
+    },
+    /// Each key clicks a fixed point instead of narrowing a grid.
+    Points { points: Vec<HintPoint> },
+}
+
+// 反序列化入口：兼容旧版组合层的 stage0 / stage1 写法
+#[derive(Deserialize, JsonSchema)]
+#[serde(tag = "mode", rename_all = "camelCase")]
+enum LayerDef {
+    Single {
+        #[schemars(range(min = 1))]
+        rows: u32,
+        #[schemars(range(min = 1))]
+        cols: u32,
+        #[schemars(inner(length(min = 1)))]
+        keys: Vec<String>,
+        /// Relative height of each row; smaller weights give finer bands.
+        #[serde(rename = "rowWeights", default)]
+        row_weights: Option<Vec<f64>>,
+        /// Relative width of each column; smaller weights give finer bands.
+        #[serde(rename = "colWeights", default)]
+        col_weights: Option<Vec<f64>>,
+        /// Grows each picked cell by this share of its size on every side.
+        #[serde(rename = "overlapRatio", default)]
+        #[schemars(range(min = 0.0, max = 0.5))]
+        overlap_ratio: Option<f64>,
     },
     Combo {
-        stage0: GridStage,
-        stage1: GridStage,
+        #[serde(default)]
+        #[schemars(length(min = 1))]
+        stages: Vec<GridStage>,
+        /// Legacy first stage; use `stages` instead.
+        #[serde(default)]
+        #[deprecated]
+        stage0: Option<GridStage>,
+        /// Legacy second stage; use `stages` instead.
+        #[serde(default)]
+        #[deprecated]
+        stage1: Option<GridStage>,
+        /// Grows each picked cell by this share of its size on every side.
+        #[serde(rename = "overlapRatio", default)]
+        #[schemars(range(min = 0.0, max = 0.5))]
+        overlap_ratio: Option<f64>,
+    },
+    Bisect {
+        #[serde(default = "default_bisect_left")]
+        #[schemars(length(min = 1))]
+        left: String,
+        #[serde(default = "default_bisect_right")]
+        #[schemars(length(min = 1))]
+        right: String,
+        #[serde(default = "default_bisect_up")]
+        #[schemars(length(min = 1))]
+        up: String,
+        #[serde(default = "default_bisect_down")]
+        #[schemars(length(min = 1))]
+        down: String,
+        #[serde(default = "default_bisect_finish")]
+        #[schemars(length(min = 1))]
+        finish: String,
     },
+    Points {
+        #[schemars(length(min = 1))]
+        points: Vec<HintPoint>,
+    },
+}
+
+fn default_bisect_left() -> String {
+    "h".to_string()
+}
+
+fn default_bisect_right() -> String {
+    "l".to_string()
+}
+
+fn default_bisect_up() -> String {
+    "k".to_string()
+}
+
+fn default_bisect_down() -> String {
+    "j".to_string()
+}
+
+fn default_bisect_finish() -> String {
+    ";".to_string()
+}
+
+impl TryFrom<LayerDef> for Layer {
+    type Error = String;
+
+    #[allow(deprecated)]
+    fn try_from(def: LayerDef) -> Result<Self, Self::Error> {
+        match def {
+            LayerDef::Single {
+                rows,
+                cols,
+                keys,
+                row_weights,
+                col_weights,
+                overlap_ratio,
+            } => Ok(Layer::Single {
+                rows,
+                cols,
+                keys,
+                row_weights,
+                col_weights,
+                overlap_ratio,
+            }),
+            LayerDef::Combo {
+                stages,
+                stage0,
+                stage1,
+                overlap_ratio,
+            } => {
+                let legacy: Vec<GridStage> = stage0.into_iter().chain(stage1).collect();
+                if !stages.is_empty() && !legacy.is_empty() {
+                    return Err("combo layer must use either stages or stage0/stage1".to_string());
+                }
+                Ok(Layer::Combo {
+                    stages: if stages.is_empty() { legacy } else { stages },
+                    overlap_ratio,
+                })
+            }
+            LayerDef::Bisect {
+                left,
+                right,
+                up,
+                down,
+                finish,
+            } => Ok(Layer::Bisect {
+                left,
+                right,
+                up,
+                down,
+                finish,
+            }),
+            LayerDef::Points { points } => Ok(Layer::Points { points }),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum PointOrigin {
+    /// `x` / `y` are physical px on the virtual desktop.
+    Absolute,
+    /// `x` / `y` are physical px from the top-left of the monitor.
+    #[default]
+    Monitor,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HintPoint {
+    #[schemars(length(min = 1))]
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub x: f64,
+    pub y: f64,
+    #[serde(default)]
+    pub origin: PointOrigin,
+    /// Only offer this point on the monitor with this name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monitor: Option<String>,
+    /// Free-form label used to group points in the overlay and settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GridStage {
+    #[schemars(range(min = 1))]
     pub rows: u32,
+    #[schemars(range(min = 1))]
     pub cols: u32,
+    #[schemars(inner(length(min = 1)))]
     pub keys: Vec<String>,
+    /// Relative height of each row; smaller weights give finer bands.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_weights: Option<Vec<f64>>,
+    /// Relative width of each column; smaller weights give finer bands.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub col_weights: Option<Vec<f64>>,
+}
+
+impl GridStage {
+    pub fn new(rows: u32, cols: u32, keys: Vec<String>) -> Self {
+        Self {
+            rows,
+            cols,
+            keys,
+            row_weights: None,
+            col_weights: None,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OverlayConfig {
     pub alpha: u8,
     pub mask_color: String,
     pub line_color: String,
     pub text_color: String,
+    #[schemars(range(min = 1))]
     pub line_width_px: u32,
     #[serde(default = "default_overlay_show_grid")]
     pub show_grid: bool,
     #[serde(default = "default_overlay_show_diagonals")]
     pub show_diagonals: bool,
+    /// Font size and line width are CSS pixels and grow with the monitor's scale factor;
+    /// off draws them at the same physical size on every monitor.
+    #[serde(default = "default_overlay_scale_with_monitor")]
+    pub scale_with_monitor: bool,
+    /// Closes the overlay after this long without an overlay key; 0 disables.
+    #[serde(default = "default_overlay_idle_timeout_ms")]
+    pub idle_timeout_ms: u32,
+    /// Closes the overlay when another window takes focus.
+    #[serde(default = "default_overlay_cancel_on_focus_change")]
+    pub cancel_on_focus_change: bool,
     pub font: OverlayFont,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OverlayFont {
     pub family: String,
+    #[schemars(range(min = 1))]
     pub size_px: u32,
 }
 
@@ -275,6 +744,18 @@ fn default_overlay_show_diagonals() -> bool {
     true
 }
 
+fn default_overlay_scale_with_monitor() -> bool {
+    true
+}
+
+fn default_overlay_idle_timeout_ms() -> u32 {
+    30_000
+}
+
+fn default_overlay_cancel_on_focus_change() -> bool {
+    true
+}
+
 fn default_locale() -> String {
     "zh-CN".to_string()
 }
@@ -286,3 +767,236 @@ pub fn default_config() -> AppConfig {
         .unwrap_or(DEFAULT_CONFIG_JSON);
     serde_json::from_str(json).expect("default-config.json should be valid AppConfig")
 }
+
+pub fn config_schema() -> Value {
+    let mut schema = serde_json::to_value(schemars::schema_for!(AppConfig))
+        .expect("AppConfig schema should serialize");
+    // 覆盖文件按对象深度合并到默认值上，因此对象字段均可省略；
+    // 数组（如 layers）整体替换，其元素仍需完整字段
+    let mut defs = schema
+        .as_object_mut()
+        .and_then(|object| object.remove("$defs"))
+        .and_then(|defs| match defs {
+            Value::Object(map) => Some(map),
+            _ => None,
+        })
+        .unwrap_or_default();
+    strip_merged_required(&mut schema, &mut defs);
+    if let Some(object) = schema.as_object_mut() {
+        object.insert(
+            "title".to_string(),
+            Value::from("Clickey settings override"),
+        );
+        if let Some(Value::Object(properties)) = object.get_mut("properties") {
+            properties.insert(
+                "$schema".to_string(),
+                serde_json::json!({ "type": "string" }),
+            );
+        }
+        object.insert("$defs".to_string(), Value::Object(defs));
+    }
+    schema
+}
+
+fn strip_merged_required(schema: &mut Value, defs: &mut Map<String, Value>) {
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+    object.remove("required");
+    if let Some(name) = object
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix("#/$defs/"))
+        .map(str::to_string)
+    {
+        if let Some(mut def) = defs.remove(&name) {
+            strip_merged_required(&mut def, defs);
+            defs.insert(name, def);
+        }
+    }
+    if let Some(Value::Object(properties)) = object.get_mut("properties") {
+        for property in properties.values_mut() {
+            strip_merged_required(property, defs);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn stage(keys: &[&str]) -> Value {
+        json!({ "rows": 1, "cols": keys.len(), "keys": keys })
+    }
+
+    #[test]
+    fn reads_legacy_stage_fields_as_stages() {
+        let legacy: Layer = serde_json::from_value(json!({
+            "mode": "combo",
+            "stage0": stage(&["q", "w"]),
+            "stage1": stage(&["e"]),
+        }))
+        .unwrap();
+        let Layer::Combo { stages, .. } = &legacy else {
+            panic!("expected combo layer");
+        };
+        assert_eq!(stages.len(), 2);
+        assert_eq!(stages[1].keys, ["e"]);
+
+        let value = serde_json::to_value(&legacy).unwrap();
+        assert_eq!(
+            value,
+            json!({ "mode": "combo", "stages": [stage(&["q", "w"]), stage(&["e"])] })
+        );
+    }
+
+    #[test]
+    fn reads_three_stage_combo() {
+        let layer: Layer = serde_json::from_value(json!({
+            "mode": "combo",
+            "stages": [stage(&["a"]), stage(&["b"]), stage(&["c"])],
+        }))
+        .unwrap();
+        assert!(matches!(layer, Layer::Combo { stages, .. } if stages.len() == 3));
+    }
+
+    #[test]
+    fn rejects_mixed_stage_fields() {
+        let mixed = serde_json::from_value::<Layer>(json!({
+            "mode": "combo",
+            "stages": [stage(&["a"])],
+            "stage0": stage(&["b"]),
+        }));
+        assert!(mixed.is_err());
+    }
+
+    #[test]
+    fn fills_default_bisect_keys() {
+        let layer: Layer = serde_json::from_value(json!({
+            "mode": "bisect",
+            "finish": "Enter",
+        }))
+        .unwrap();
+        let Layer::Bisect {
+            left, down, finish, ..
+        } = layer
+        else {
+            panic!("expected bisect layer");
+        };
+        assert_eq!((left.as_str(), down.as_str()), ("h", "j"));
+        assert_eq!(finish, "Enter");
+    }
+
+    #[test]
+    fn keeps_weights_in_camel_case() {
+        let single = json!({
+            "mode": "single",
+            "rows": 3,
+            "cols": 1,
+            "keys": ["a", "b", "c"],
+            "rowWeights": [1.0, 1.0, 3.0],
+        });
+        let layer: Layer = serde_json::from_value(single.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&layer).unwrap(), single);
+
+        let combo = json!({
+            "mode": "combo",
+            "stages": [{ "rows": 1, "cols": 2, "keys": ["a", "b"], "colWeights": [2.0, 1.0] }],
+        });
+        let layer: Layer = serde_json::from_value(combo.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&layer).unwrap(), combo);
+    }
+
+    #[test]
+    fn reads_points_with_default_origin() {
+        let layer: Layer = serde_json::from_value(json!({
+            "mode": "points",
+            "points": [
+                { "key": "t", "name": "tab bar", "x": 400, "y": 12, "group": "browser" },
+                { "key": "b", "x": 10.5, "y": 20, "origin": "absolute", "monitor": "DP-1" },
+            ],
+        }))
+        .unwrap();
+        let Layer::Points { points } = &layer else {
+            panic!("expected points layer");
+        };
+        assert_eq!(points[0].origin, PointOrigin::Monitor);
+        assert_eq!(points[1].origin, PointOrigin::Absolute);
+        assert_eq!(points[1].monitor.as_deref(), Some("DP-1"));
+
+        let value = serde_json::to_value(&layer).unwrap();
+        assert_eq!(value["points"][0]["origin"], "monitor");
+        assert!(value["points"][0].get("monitor").is_none());
+    }
+
+    #[test]
+    fn applies_monitor_profiles() {
+        let mut config = default_config();
+        let laptop = serde_json::from_value::<MonitorProfile>(json!({
+            "monitor": "eDP-1",
+            "layers": [{ "mode": "single", "rows": 1, "cols": 2, "keys": ["a", "b"] }],
+            "fontSizePx": 10,
+        }))
+        .unwrap();
+        let ultrawide = serde_json::from_value::<MonitorProfile>(json!({
+            "monitor": "5120X1440",
+            "lineWidthPx": 3,
+        }))
+        .unwrap();
+        config.monitor.profiles = vec![ultrawide, laptop];
+
+        // 名称优先于分辨率；尺寸原样保留为 CSS 像素
+        let on_laptop = config.for_monitor(Some("eDP-1"), 5120, 1440);
+        assert_eq!(on_laptop.layers.len(), 1);
+        assert_eq!(on_laptop.overlay.font.size_px, 10);
+        assert_eq!(
+            on_laptop.overlay.line_width_px,
+            config.overlay.line_width_px
+        );
+
+        let on_ultrawide = config.for_monitor(Some("DP-2"), 5120, 1440);
+        assert_eq!(on_ultrawide.layers.len(), config.layers.len());
+        assert_eq!(on_ultrawide.overlay.line_width_px, 3);
+
+        let unmatched = config.for_monitor(None, 1920, 1080);
+        assert_eq!(unmatched.overlay.font.size_px, config.overlay.font.size_px);
+    }
+
+    #[test]
+    fn schema_documents_stage_list() {
+        let schema = config_schema().to_string();
+        assert!(schema.contains("\"stages\""));
+        assert!(schema.contains("\"stage0\""));
+    }
+
+    #[test]
+    fn schema_matches_validated_ranges() {
+        let schema = config_schema();
+        let defs = &schema["$defs"];
+        let mouse = &defs["MouseConfig"]["properties"];
+        assert_eq!(mouse["jitterRatio"]["maximum"], json!(0.2));
+        assert_eq!(mouse["maxSteps"]["minimum"], json!(2));
+        for key in [
+            "durationRandomness",
+            "stepRandomness",
+            "durationDistanceBoost",
+            "stepDistanceBoost",
+        ] {
+            assert_eq!(mouse[key]["exclusiveMaximum"], json!(1.0), "{}", key);
+            assert_eq!(mouse[key]["minimum"], json!(0.0), "{}", key);
+        }
+
+        // 按对象合并的部分都可省略，整体替换的数组元素仍要求完整字段
+        assert!(schema.get("required").is_none());
+        for name in [
+            "MouseConfig",
+            "OverlayConfig",
+            "HotkeysConfig",
+            "MonitorConfig",
+        ] {
+            assert!(defs[name].get("required").is_none(), "{}", name);
+        }
+        assert!(defs["GridStage"].get("required").is_some());
+    }
+}
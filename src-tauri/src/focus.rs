@@ -0,0 +1,274 @@
+use crate::monitors::Region;
+
+// 窗口几何信息的来源，SystemWindows 向窗口管理器查询
+pub trait WindowQuery {
+    // 前台窗口的物理像素边界，包含窗口边框
+    fn focused_window(&self) -> Option<Region>;
+
+    // 用来判断之后焦点是否换了窗口
+    fn focused_handle(&self) -> Option<u64> {
+        None
+    }
+}
+
+// X11 下整个生命周期只持有一个连接
+pub struct SystemWindows {
+    #[cfg(target_os = "linux")]
+    x11: Option<X11Windows>,
+}
+
+impl SystemWindows {
+    pub fn connect() -> Self {
+        Self {
+            #[cfg(target_os = "linux")]
+            x11: X11Windows::connect(),
+        }
+    }
+}
+
+impl WindowQuery for SystemWindows {
+    fn focused_window(&self) -> Option<Region> {
+        platform_focused_window(self)
+    }
+
+    fn focused_handle(&self) -> Option<u64> {
+        platform_focused_handle(self)
+    }
+}
+
+// 窗口太小时网格没有意义，回到显示器
+const MIN_WINDOW_PX: f64 = 32.0;
+
+// 前台窗口裁到它主要所在的显示器，返回 (显示器序号, 区域)；没有可用窗口时返回 None
+pub fn focused_region(query: &dyn WindowQuery, monitors: &[Region]) -> Option<(usize, Region)> {
+    let window = query.focused_window()?;
+    // 最大化窗口的边框常伸出屏幕外几像素，裁剪到所在显示器
+    let index = monitors
+        .iter()
+        .enumerate()
+        .filter_map(|(index, monitor)| {
+            let clipped = monitor.intersection(&window)?;
+            Some((index, clipped.width * clipped.height))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(index, _)| index)
+        .or_else(|| monitors.is_empty().then_some(0))?;
+    let region = match monitors.get(index) {
+        Some(monitor) => monitor.intersection(&window)?,
+        None => window,
+    };
+    if region.width < MIN_WINDOW_PX || region.height < MIN_WINDOW_PX {
+        return None;
+    }
+    Some((index, region))
+}
+
+#[cfg(windows)]
+fn platform_focused_window(_windows: &SystemWindows) -> Option<Region> {
+    use windows::Win32::Foundation::RECT;
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowRect};
+
+    // SAFETY: both calls only read window state; the handle is checked before use.
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if hwnd.is_invalid() {
+            return None;
+        }
+        let mut rect = RECT::default();
+        GetWindowRect(hwnd, &mut rect).ok()?;
+        let width = rect.right - rect.left;
+        let height = rect.bottom - rect.top;
+        if width <= 0 || height <= 0 {
+            return None;
+        }
+        Some(Region {
+            x: rect.left as f64,
+            y: rect.top as f64,
+            width: width as f64,
+            height: height as f64,
+        })
+    }
+}
+
+// X11 下通过 EWMH 的 _NET_ACTIVE_WINDOW 读取前台窗口；Wayland 无法连接时为 None
+#[cfg(target_os = "linux")]
+struct X11Windows {
+    conn: x11rb::rust_connection::RustConnection,
+    root: u32,
+    active_window: u32,
+    frame_extents: Option<u32>,
+}
+
+#[cfg(target_os = "linux")]
+impl X11Windows {
+    fn connect() -> Option<Self> {
+        use x11rb::connection::Connection;
+        use x11rb::protocol::xproto::ConnectionExt;
+
+        let (conn, screen_num) = x11rb::connect(None).ok()?;
+        let root = conn.setup().roots.get(screen_num)?.root;
+        let atom = |name: &[u8]| -> Option<u32> {
+            Some(conn.intern_atom(false, name).ok()?.reply().ok()?.atom)
+        };
+        let active_window = atom(b"_NET_ACTIVE_WINDOW")?;
+        let frame_extents = atom(b"_NET_FRAME_EXTENTS");
+        Some(Self {
+            conn,
+            root,
+            active_window,
+            frame_extents,
+        })
+    }
+
+    fn active_window(&self) -> Option<u32> {
+        use x11rb::protocol::xproto::{AtomEnum, ConnectionExt};
+
+        let window = self
+            .conn
+            .get_property(false, self.root, self.active_window, AtomEnum::WINDOW, 0, 1)
+            .ok()?
+            .reply()
+            .ok()?
+            .value32()?
+            .next()?;
+        (window != 0).then_some(window)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn platform_focused_window(windows: &SystemWindows) -> Option<Region> {
+    use x11rb::protocol::xproto::{AtomEnum, ConnectionExt};
+
+    let x11 = windows.x11.as_ref()?;
+    let conn = &x11.conn;
+    let window = x11.active_window()?;
+
+    let geometry = conn.get_geometry(window).ok()?.reply().ok()?;
+    let origin = conn
+        .translate_coordinates(window, x11.root, 0, 0)
+        .ok()?
+        .reply()
+        .ok()?;
+
+    // 窗口管理器画的标题栏和边框不属于客户区，按 _NET_FRAME_EXTENTS 补上
+    let extents: Vec<u32> = x11
+        .frame_extents
+        .and_then(|frame_atom| {
+            conn.get_property(false, window, frame_atom, AtomEnum::CARDINAL, 0, 4)
+                .ok()?
+                .reply()
+                .ok()
+        })
+        .and_then(|reply| reply.value32().map(|values| values.collect()))
+        .unwrap_or_default();
+    let (left, right, top, bottom) = match extents[..] {
+        [left, right, top, bottom] => (left, right, top, bottom),
+        _ => (0, 0, 0, 0),
+    };
+
+    Some(Region {
+        x: origin.dst_x as f64 - left as f64,
+        y: origin.dst_y as f64 - top as f64,
+        width: (geometry.width as u32 + left + right) as f64,
+        height: (geometry.height as u32 + top + bottom) as f64,
+    })
+}
+
+#[cfg(windows)]
+fn platform_focused_handle(_windows: &SystemWindows) -> Option<u64> {
+    use windows::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
+
+    // SAFETY: only reads the foreground window handle.
+    let hwnd = unsafe { GetForegroundWindow() };
+    (!hwnd.is_invalid()).then_some(hwnd.0 as usize as u64)
+}
+
+#[cfg(target_os = "linux")]
+fn platform_focused_handle(windows: &SystemWindows) -> Option<u64> {
+    windows.x11.as_ref()?.active_window().map(u64::from)
+}
+
+// 其他平台暂不读取前台窗口，按显示器处理
+#[cfg(not(any(windows, target_os = "linux")))]
+fn platform_focused_window(_windows: &SystemWindows) -> Option<Region> {
+    None
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn platform_focused_handle(_windows: &SystemWindows) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeWindows(Option<Region>);
+
+    impl WindowQuery for FakeWindows {
+        fn focused_window(&self) -> Option<Region> {
+            self.0.clone()
+        }
+    }
+
+    fn region(x: f64, y: f64, width: f64, height: f64) -> Region {
+        Region {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    fn monitors() -> Vec<Region> {
+        vec![
+            region(0.0, 0.0, 1920.0, 1080.0),
+            region(1920.0, 0.0, 2560.0, 1440.0),
+        ]
+    }
+
+    #[test]
+    fn uses_the_focused_window() {
+        let query = FakeWindows(Some(region(2000.0, 100.0, 1200.0, 800.0)));
+        assert_eq!(
+            focused_region(&query, &monitors()),
+            Some((1, region(2000.0, 100.0, 1200.0, 800.0)))
+        );
+    }
+
+    #[test]
+    fn clips_to_the_monitor_holding_most_of_the_window() {
+        // 最大化窗口四周多出 8px 边框
+        let maximized = FakeWindows(Some(region(-8.0, -8.0, 1936.0, 1096.0)));
+        assert_eq!(
+            focused_region(&maximized, &monitors()),
+            Some((0, region(0.0, 0.0, 1920.0, 1080.0)))
+        );
+
+        let straddling = FakeWindows(Some(region(1500.0, 200.0, 1000.0, 600.0)));
+        assert_eq!(
+            focused_region(&straddling, &monitors()),
+            Some((1, region(1920.0, 200.0, 580.0, 600.0)))
+        );
+    }
+
+    #[test]
+    fn falls_back_without_a_usable_window() {
+        assert_eq!(focused_region(&FakeWindows(None), &monitors()), None);
+
+        let tiny = FakeWindows(Some(region(10.0, 10.0, 20.0, 400.0)));
+        assert_eq!(focused_region(&tiny, &monitors()), None);
+
+        let offscreen = FakeWindows(Some(region(-5000.0, 0.0, 800.0, 600.0)));
+        assert_eq!(focused_region(&offscreen, &monitors()), None);
+    }
+
+    #[test]
+    fn keeps_the_window_when_no_monitor_is_known() {
+        let query = FakeWindows(Some(region(10.0, 20.0, 640.0, 480.0)));
+        assert_eq!(
+            focused_region(&query, &[]),
+            Some((0, region(10.0, 20.0, 640.0, 480.0)))
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use toml_edit::{DocumentMut, Item, TableLike};
+
+const SCHEMA_KEY: &str = "$schema";
+const YAML_SCHEMA_COMMENT: &str = "# yaml-language-server: $schema=";
+
+// 覆盖文件支持的格式；TOML / YAML 允许写注释
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ConfigFormat::Json => "json",
+            ConfigFormat::Toml => "toml",
+            ConfigFormat::Yaml => "yaml",
+        }
+    }
+
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        let (_, extension) = name.rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Some(ConfigFormat::Json),
+            "toml" => Some(ConfigFormat::Toml),
+            "yaml" | "yml" => Some(ConfigFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    // 先看扩展名，没有时再根据内容判断
+    pub fn detect(file_name: Option<&str>, contents: &str) -> Self {
+        file_name
+            .and_then(Self::from_file_name)
+            .unwrap_or_else(|| Self::sniff(contents))
+    }
+
+    fn sniff(contents: &str) -> Self {
+        let trimmed = strip_bom(contents).trim_start();
+        if trimmed.starts_with('{') {
+            return ConfigFormat::Json;
+        }
+        if toml::from_str::<toml::Table>(trimmed).is_ok() {
+            return ConfigFormat::Toml;
+        }
+        ConfigFormat::Yaml
+    }
+}
+
+fn strip_bom(contents: &str) -> &str {
+    contents.strip_prefix('\u{FEFF}').unwrap_or(contents)
+}
+
+// 不限定顶层类型，JSON Patch 文档是数组
+pub fn parse_document(contents: &str, format: ConfigFormat) -> Result<Value, String> {
+    let contents = strip_bom(contents);
+    match format {
+        ConfigFormat::Json => serde_json::from_str::<Value>(contents).map_err(|e| e.to_string()),
+        ConfigFormat::Toml => toml::from_str::<Value>(contents).map_err(|e| e.to_string()),
+        ConfigFormat::Yaml => {
+            // 空 YAML 文档视为空覆盖
+            if contents.trim().is_empty() {
+                Ok(Value::Object(Default::default()))
+            } else {
+                serde_yaml::from_str::<Value>(contents).map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
+pub fn parse_overrides(contents: &str, format: ConfigFormat) -> Result<Value, String> {
+    let value = parse_document(contents, format)?;
+    if !value.is_object() {
+        return Err(format!(
+            "override {} must be an object",
+            format.extension().to_ascii_uppercase()
+        ));
+    }
+    Ok(value)
+}
+
+pub fn format_overrides(overrides: &Value, format: ConfigFormat) -> Result<String, String> {
+    match format {
+        ConfigFormat::Json => serde_json::to_string_pretty(overrides).map_err(|e| e.to_string()),
+        ConfigFormat::Toml => {
+            // TOML / YAML 没有 `$schema` 字段约定，改用编辑器识别的注释指令
+            let (body, schema) = split_schema_ref(overrides);
+            let text = toml::to_string_pretty(&body).map_err(|e| e.to_string())?;
+            Ok(match schema {
+                Some(schema) => format!("#:schema {}\n\n{}", schema, text),
+                None => text,
+            })
+        }
+        ConfigFormat::Yaml => {
+            let (body, schema) = split_schema_ref(overrides);
+            let text = serde_yaml::to_string(&body).map_err(|e| e.to_string())?;
+            Ok(match schema {
+                Some(schema) => format!("{}{}\n{}", YAML_SCHEMA_COMMENT, schema, text),
+                None => text,
+            })
+        }
+    }
+}
+
+// 原地更新已有的 TOML 覆盖文件：只改动增删改过的键，其余键的注释和排版保持不变
+pub fn update_toml_overrides(existing: &str, overrides: &Value) -> Result<String, String> {
+    let mut document = strip_bom(existing)
+        .parse::<DocumentMut>()
+        .map_err(|e| e.to_string())?;
+    let before = parse_document(existing, ConfigFormat::Toml)?;
+    let (body, _) = split_schema_ref(overrides);
+    let fresh = toml::to_string_pretty(&body)
+        .map_err(|e| e.to_string())?
+        .parse::<DocumentMut>()
+        .map_err(|e| e.to_string())?;
+    sync_table(document.as_table_mut(), &before, &body, fresh.as_table());
+    Ok(document.to_string())
+}
+
+fn sync_table(target: &mut dyn TableLike, before: &Value, after: &Value, fresh: &dyn TableLike) {
+    let empty = serde_json::Map::new();
+    let before = before.as_object().unwrap_or(&empty);
+    let after = after.as_object().unwrap_or(&empty);
+
+    let stale: Vec<String> = target
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| !after.contains_key(key))
+        .collect();
+    for key in stale {
+        target.remove(&key);
+    }
+
+    for (key, value) in after {
+        // 值没变的键连同注释原样保留
+        if before.get(key) == Some(value) {
+            continue;
+        }
+        let Some(replacement) = fresh.get(key) else {
+            continue;
+        };
+        match target.get_mut(key) {
+            Some(item) if value.is_object() && item.is_table_like() => {
+                if let (Some(table), Some(fresh_table)) =
+                    (item.as_table_like_mut(), replacement.as_table_like())
+                {
+                    let previous = before.get(key).unwrap_or(&Value::Null);
+                    sync_table(table, previous, value, fresh_table);
+                }
+            }
+            Some(item) if item.is_value() => {
+                // 内联表里只能放值；沿用原来的行尾注释
+                if let Ok(mut updated) = replacement.clone().into_value() {
+                    if let Some(current) = item.as_value() {
+                        *updated.decor_mut() = current.decor().clone();
+                    }
+                    *item = Item::Value(updated);
+                }
+            }
+            Some(item) => *item = replacement.clone(),
+            None => {
+                target.insert(key, replacement.clone());
+            }
+        }
+    }
+}
+
+// 重写会丢掉的注释；format_overrides 写入的 schema 注释不算，引号里的 # 跳过。
+// 拿不准的一律当作注释，手写的文件不会被误覆盖
+pub fn yaml_has_comments(contents: &str) -> bool {
+    strip_bom(contents).lines().any(|line| {
+        if line.starts_with(YAML_SCHEMA_COMMENT) {
+            return false;
+        }
+        let mut quote = None;
+        let mut previous = ' ';
+        for c in line.chars() {
+            match (quote, c) {
+                (None, '#') if previous.is_whitespace() => return true,
+                (None, '\'' | '"') if previous.is_whitespace() || "[{,:-".contains(previous) => {
+                    quote = Some(c)
+                }
+                (Some(open), _) if c == open => quote = None,
+                _ => {}
+            }
+            previous = c;
+        }
+        false
+    })
+}
+
+fn split_schema_ref(overrides: &Value) -> (Value, Option<String>) {
+    let mut body = overrides.clone();
+    let schema = body
+        .as_object_mut()
+        .and_then(|object| object.remove(SCHEMA_KEY))
+        .and_then(|value| value.as_str().map(str::to_string));
+    (body, schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DEFAULT_CONFIG_JSON;
+    use serde_json::json;
+
+    const FORMATS: [ConfigFormat; 3] = [ConfigFormat::Json, ConfigFormat::Toml, ConfigFormat::Yaml];
+
+    fn sample_overrides() -> Value {
+        json!({
+            "app": { "locale": "en-US" },
+            "mouse": { "jitterRatio": 0.05, "maxSteps": 180, "smoothMove": false },
+            "layers": [
+                {
+                    "mode": "single",
+                    "rows": 2,
+                    "cols": 3,
+                    "keys": ["q", ",", ".", ";", "/", "'"]
+                }
+            ],
+            "overlay": { "font": { "family": "Segoe UI", "sizePx": 14 } }
+        })
+    }
+
+    fn default_overrides() -> Value {
+        serde_json::from_str(DEFAULT_CONFIG_JSON.trim_start_matches('\u{FEFF}')).unwrap()
+    }
+
+    #[test]
+    fn round_trips_through_every_format() {
+        for overrides in [sample_overrides(), default_overrides()] {
+            for format in FORMATS {
+                let text = format_overrides(&overrides, format).unwrap();
+                let parsed = parse_overrides(&text, format).unwrap();
+                assert_eq!(parsed, overrides, "{:?} round trip", format);
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_schema_reference_as_comment_outside_json() {
+        let mut overrides = sample_overrides();
+        overrides["$schema"] = json!("./settings.schema.json");
+
+        let toml_text = format_overrides(&overrides, ConfigFormat::Toml).unwrap();
+        assert!(toml_text.starts_with("#:schema ./settings.schema.json"));
+        let yaml_text = format_overrides(&overrides, ConfigFormat::Yaml).unwrap();
+        assert!(yaml_text.starts_with("# yaml-language-server: $schema=./settings.schema.json"));
+
+        assert_eq!(
+            parse_overrides(&toml_text, ConfigFormat::Toml).unwrap(),
+            sample_overrides()
+        );
+        assert_eq!(
+            parse_overrides(&yaml_text, ConfigFormat::Yaml).unwrap(),
+            sample_overrides()
+        );
+    }
+
+    #[test]
+    fn detects_format_by_extension_then_content() {
+        assert_eq!(
+            ConfigFormat::detect(Some("keys.YML"), "{}"),
+            ConfigFormat::Yaml
+        );
+        assert_eq!(
+            ConfigFormat::detect(Some("settings.override.toml"), ""),
+            ConfigFormat::Toml
+        );
+        assert_eq!(
+            ConfigFormat::detect(None, "  {\"app\": {}}"),
+            ConfigFormat::Json
+        );
+        assert_eq!(
+            ConfigFormat::detect(None, "# layout\n[nudge]\nstepPx = 3\n"),
+            ConfigFormat::Toml
+        );
+        assert_eq!(
+            ConfigFormat::detect(Some("overrides.txt"), "nudge:\n  stepPx: 3\n"),
+            ConfigFormat::Yaml
+        );
+    }
+
+    #[test]
+    fn updates_toml_in_place_and_keeps_comments() {
+        let existing = "#:schema ./settings.schema.json\n\n# 我的布局\n[app]\nlocale = \"en-US\" # 英文界面\n\n[mouse]\n# 慢一点\nmaxSteps = 180\nsmoothMove = false\n\n[nudge]\nstepPx = 3\n";
+        let mut overrides = json!({
+            "$schema": "./settings.schema.json",
+            "app": { "locale": "zh-CN" },
+            "mouse": { "maxSteps": 180, "smoothMove": true },
+            "overlay": { "font": { "sizePx": 14 } }
+        });
+
+        let text = update_toml_overrides(existing, &overrides).unwrap();
+        assert!(text.starts_with("#:schema ./settings.schema.json"));
+        assert!(text.contains("# 我的布局"));
+        assert!(text.contains("locale = \"zh-CN\" # 英文界面"));
+        assert!(text.contains("# 慢一点\nmaxSteps = 180"));
+        assert!(!text.contains("[nudge]"));
+
+        overrides.as_object_mut().unwrap().remove("$schema");
+        assert_eq!(
+            parse_overrides(&text, ConfigFormat::Toml).unwrap(),
+            overrides
+        );
+    }
+
+    #[test]
+    fn finds_yaml_comments_outside_quotes() {
+        let generated = format_overrides(
+            &json!({ "$schema": "./settings.schema.json", "overlay": { "lineColor": "#00ff00" } }),
+            ConfigFormat::Yaml,
+        )
+        .unwrap();
+        assert!(!yaml_has_comments(&generated));
+        assert!(!yaml_has_comments(
+            "overlay:\n  textColor: '#fff'\n  key: a#b\n"
+        ));
+
+        assert!(yaml_has_comments("# 我的设置\nnudge:\n  stepPx: 3\n"));
+        assert!(yaml_has_comments("nudge:\n  stepPx: 3 # 小步\n"));
+    }
+
+    #[test]
+    fn rejects_non_object_documents() {
+        assert!(parse_overrides("[1, 2]", ConfigFormat::Json).is_err());
+        assert!(parse_overrides("- a\n- b\n", ConfigFormat::Yaml).is_err());
+        assert_eq!(parse_overrides("", ConfigFormat::Yaml).unwrap(), json!({}));
+    }
+}
@@ -0,0 +1,374 @@
+use crate::analyze::{normalize_key, reserved_keys};
+use crate::config::{AppConfig, GridStage, Layer};
+use serde::{Deserialize, Serialize};
+
+// 生成的层数上限，防止目标尺寸过小时无限细分
+const MAX_GENERATED_LAYERS: usize = 8;
+// 两个方向都至少能切出这么多格时才使用组合层
+const COMBO_MIN_SPLITS: f64 = 6.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyboardLayout {
+    Qwerty,
+    Dvorak,
+    Colemak,
+    Azerty,
+}
+
+impl KeyboardLayout {
+    // 三排字母区，每排 10 个键，前 5 个归左手
+    fn rows(self) -> [&'static str; 3] {
+        match self {
+            KeyboardLayout::Qwerty => ["qwertyuiop", "asdfghjkl;", "zxcvbnm,./"],
+            KeyboardLayout::Dvorak => ["',.pyfgcrl", "aoeuidhtns", ";qjkxbmwvz"],
+            KeyboardLayout::Colemak => ["qwfpgjluy;", "arstdhneio", "zxcvbkm,./"],
+            KeyboardLayout::Azerty => ["azertyuiop", "qsdfghjklm", "wxcvbn,;:!"],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HandPreference {
+    // 组合层左手选列、右手选行
+    Split,
+    Left,
+    Right,
+}
+
+impl HandPreference {
+    fn columns(self) -> std::ops::Range<usize> {
+        match self {
+            HandPreference::Split => 0..10,
+            HandPreference::Left => 0..5,
+            HandPreference::Right => 5..10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutRequest {
+    pub keyboard: KeyboardLayout,
+    pub hands: HandPreference,
+    pub monitor_width: u32,
+    pub monitor_height: u32,
+    pub target_cell_px: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedLayout {
+    pub layers: Vec<Layer>,
+    pub cell_width: f64,
+    pub cell_height: f64,
+}
+
+// 键盘一行中属于该手且可用的按键
+fn hand_rows(
+    keyboard: KeyboardLayout,
+    hands: HandPreference,
+    is_usable: &dyn Fn(&str) -> bool,
+) -> Vec<Vec<String>> {
+    keyboard
+        .rows()
+        .iter()
+        .map(|row| {
+            row.chars()
+                .skip(hands.columns().start)
+                .take(hands.columns().len())
+                .map(|key| key.to_string())
+                .filter(|key| is_usable(key))
+                .collect()
+        })
+        .collect()
+}
+
+// 按列优先排列，与默认组合层一致
+fn column_major(rows: &[Vec<String>]) -> Vec<String> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    (0..width)
+        .flat_map(|col| rows.iter().filter_map(move |row| row.get(col).cloned()))
+        .collect()
+}
+
+fn splits_needed(length: f64, target: f64) -> usize {
+    (length / target).ceil().max(1.0) as usize
+}
+
+fn combo_layer(
+    request: &LayoutRequest,
+    is_usable: &dyn Fn(&str) -> bool,
+    width: f64,
+    height: f64,
+    target: f64,
+) -> Option<Layer> {
+    let left = column_major(&hand_rows(
+        request.keyboard,
+        HandPreference::Left,
+        is_usable,
+    ));
+    let right = column_major(&hand_rows(
+        request.keyboard,
+        HandPreference::Right,
+        is_usable,
+    ));
+    let cols = left.len().min(splits_needed(width, target));
+    let rows = right.len().min(splits_needed(height, target));
+    if cols < 2 || rows < 2 {
+        return None;
+    }
+
+    Some(Layer::Combo {
+        stages: vec![
+            GridStage::new(1, cols as u32, left[..cols].to_vec()),
+            GridStage::new(rows as u32, 1, right[..rows].to_vec()),
+        ],
+        overlap_ratio: None,
+    })
+}
+
+fn single_layer(
+    rows: &[Vec<String>],
+    width: f64,
+    height: f64,
+    target: f64,
+) -> Option<(u32, u32, Vec<String>)> {
+    // 行数不足三排时优先用主键位行
+    let row_indices: &[usize] = match splits_needed(height, target) {
+        1 => &[1],
+        2 => &[0, 1],
+        _ => &[0, 1, 2],
+    };
+    let chosen: Vec<&Vec<String>> = row_indices
+        .iter()
+        .filter_map(|index| rows.get(*index))
+        .filter(|row| !row.is_empty())
+        .collect();
+    let cols = chosen
+        .iter()
+        .map(|row| row.len())
+        .min()
+        .unwrap_or(0)
+        .min(splits_needed(width, target));
+    if chosen.is_empty() || cols == 0 || chosen.len() * cols < 2 {
+        return None;
+    }
+
+    let keys = chosen
+        .iter()
+        .flat_map(|row| row[..cols].iter().cloned())
+        .collect();
+    Some((chosen.len() as u32, cols as u32, keys))
+}
+
+// 生成的层把显示器缩小到不超过 target_cell_px 的格子；与控制键、切换显示器、
+// 微调方向键冲突或无法注册的按键会被跳过
+pub fn generate_layers(
+    request: &LayoutRequest,
+    config: &AppConfig,
+    is_bindable: &dyn Fn(&str) -> bool,
+) -> Result<GeneratedLayout, String> {
+    if request.monitor_width == 0 || request.monitor_height == 0 {
+        return Err("monitor size must be > 0".to_string());
+    }
+    if request.target_cell_px == 0 {
+        return Err("target cell size must be > 0".to_string());
+    }
+
+    let reserved = reserved_keys(config);
+    let is_usable = |key: &str| !reserved.contains(&normalize_key(key)) && is_bindable(key);
+
+    let target = request.target_cell_px as f64;
+    let mut width = request.monitor_width as f64;
+    let mut height = request.monitor_height as f64;
+    let mut layers = Vec::new();
+
+    if request.hands == HandPreference::Split
+        && width / target >= COMBO_MIN_SPLITS
+        && height / target >= COMBO_MIN_SPLITS
+    {
+        if let Some(layer) = combo_layer(request, &is_usable, width, height, target) {
+            if let Layer::Combo { stages, .. } = &layer {
+                for stage in stages {
+                    width /= stage.cols as f64;
+                    height /= stage.rows as f64;
+                }
+            }
+            layers.push(layer);
+        }
+    }
+
+    let rows = hand_rows(request.keyboard, request.hands, &is_usable);
+    while width > target || height > target {
+        if layers.len() >= MAX_GENERATED_LAYERS {
+            return Err(format!(
+                "target cell size {}px needs more than {} layers",
+                request.target_cell_px, MAX_GENERATED_LAYERS
+            ));
+        }
+        let (grid_rows, grid_cols, keys) = single_layer(&rows, width, height, target)
+            .ok_or_else(|| "not enough usable keys for this keyboard layout".to_string())?;
+        width /= grid_cols as f64;
+        height /= grid_rows as f64;
+        layers.push(Layer::Single {
+            rows: grid_rows,
+            cols: grid_cols,
+            keys,
+            row_weights: None,
+            col_weights: None,
+            overlap_ratio: None,
+        });
+    }
+
+    if layers.is_empty() {
+        return Err("monitor is already smaller than the target cell size".to_string());
+    }
+
+    Ok(GeneratedLayout {
+        layers,
+        cell_width: width,
+        cell_height: height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyze::analyze_keys;
+    use crate::config::default_config;
+
+    const KEYBOARDS: [KeyboardLayout; 4] = [
+        KeyboardLayout::Qwerty,
+        KeyboardLayout::Dvorak,
+        KeyboardLayout::Colemak,
+        KeyboardLayout::Azerty,
+    ];
+    const HANDS: [HandPreference; 3] = [
+        HandPreference::Split,
+        HandPreference::Left,
+        HandPreference::Right,
+    ];
+
+    fn request(keyboard: KeyboardLayout, hands: HandPreference, target: u32) -> LayoutRequest {
+        LayoutRequest {
+            keyboard,
+            hands,
+            monitor_width: 2560,
+            monitor_height: 1440,
+            target_cell_px: target,
+        }
+    }
+
+    fn key_count_matches(layer: &Layer) -> bool {
+        match layer {
+            Layer::Single {
+                rows, cols, keys, ..
+            } => keys.len() == (rows * cols) as usize,
+            Layer::Combo { stages, .. } => stages
+                .iter()
+                .all(|stage| stage.keys.len() == (stage.rows * stage.cols) as usize),
+            Layer::Bisect { .. } | Layer::Points { .. } => false,
+        }
+    }
+
+    #[test]
+    fn generates_conflict_free_layers_for_every_layout() {
+        let mut config = default_config();
+        for keyboard in KEYBOARDS {
+            for hands in HANDS {
+                for target in [1, 8, 40, 400] {
+                    let generated =
+                        generate_layers(&request(keyboard, hands, target), &config, &|_| true)
+                            .unwrap();
+                    assert!(generated.cell_width <= target as f64);
+                    assert!(generated.cell_height <= target as f64);
+                    assert!(generated.layers.iter().all(key_count_matches));
+
+                    config.layers = generated.layers;
+                    let report = analyze_keys(&config, &|_| true);
+                    assert!(report.errors.is_empty(), "{:?}", report.errors);
+                    assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn split_hands_start_with_combo_layer() {
+        let config = default_config();
+        let generated = generate_layers(
+            &request(KeyboardLayout::Qwerty, HandPreference::Split, 8),
+            &config,
+            &|_| true,
+        )
+        .unwrap();
+        match &generated.layers[0] {
+            Layer::Combo { stages, .. } => {
+                assert_eq!(stages[0].keys[..3], ["q", "a", "z"]);
+                assert_eq!(stages[1].keys[..3], ["y", "h", "n"]);
+            }
+            _ => panic!("expected combo layer"),
+        }
+    }
+
+    #[test]
+    fn skips_control_and_unbindable_keys() {
+        let mut config = default_config();
+        config.hotkeys.controls.undo = "A".to_string();
+        let generated = generate_layers(
+            &request(KeyboardLayout::Azerty, HandPreference::Left, 40),
+            &config,
+            &|key| key != "z",
+        )
+        .unwrap();
+        for layer in &generated.layers {
+            if let Layer::Single { keys, .. } = layer {
+                assert!(!keys.iter().any(|key| key == "a" || key == "z"));
+            }
+        }
+    }
+
+    #[test]
+    fn skips_monitor_switch_keys() {
+        let mut config = default_config();
+        config.monitor.direction_keys.left = "h".to_string();
+        config.monitor.direction_keys.down = "j".to_string();
+        config.monitor.direction_keys.up = "k".to_string();
+        config.monitor.direction_keys.right = "l".to_string();
+        config.monitor.previous_key = "p".to_string();
+        config.monitor.jump_keys = vec!["F1".to_string(), "F2".to_string()];
+
+        for hands in [HandPreference::Split, HandPreference::Left] {
+            let generated = generate_layers(
+                &request(KeyboardLayout::Qwerty, hands, 40),
+                &config,
+                &|_| true,
+            )
+            .unwrap();
+            let mut checked = config.clone();
+            checked.layers = generated.layers;
+            // 默认本地层本身就用到 hjkl，这里只检查生成的主层
+            checked.local.layers.clear();
+            let report = analyze_keys(&checked, &|_| true);
+            assert!(report.errors.is_empty(), "{:?}", report.errors);
+        }
+        // 右手主键位行只剩 ;，宁可报错也不生成冲突的布局
+        let right = request(KeyboardLayout::Qwerty, HandPreference::Right, 40);
+        assert!(generate_layers(&right, &config, &|_| true).is_err());
+    }
+
+    #[test]
+    fn rejects_empty_sizes() {
+        let config = default_config();
+        let mut invalid = request(KeyboardLayout::Qwerty, HandPreference::Split, 0);
+        assert!(generate_layers(&invalid, &config, &|_| true).is_err());
+        invalid.target_cell_px = 10;
+        invalid.monitor_width = 0;
+        assert!(generate_layers(&invalid, &config, &|_| true).is_err());
+        invalid.monitor_width = 8;
+        invalid.monitor_height = 8;
+        assert!(generate_layers(&invalid, &config, &|_| true).is_err());
+    }
+}
@@ -1,18 +1,47 @@
+mod analyze;
+mod anchor;
 mod config;
-
-use config::{default_config, AppConfig, Layer, MouseConfig};
+mod focus;
+mod format;
+mod layout;
+mod monitors;
+mod overrides;
+mod precision;
+mod session;
+
+use analyze::{analyze_keys, normalize_key, KeyConflictReport};
+use anchor::{landing_bounds, ClickAnchor};
+use config::{
+    config_schema, default_config, AppConfig, Layer, MonitorConfig, MouseConfig, PointOrigin,
+    StartStrategy, MAX_OVERLAP_RATIO,
+};
 use enigo::{Enigo, MouseButton, MouseControllable};
+use focus::{focused_region, SystemWindows, WindowQuery};
+use format::{
+    format_overrides, parse_document, parse_overrides, update_toml_overrides, yaml_has_comments,
+    ConfigFormat,
+};
+use layout::{generate_layers, GeneratedLayout, LayoutRequest};
+use monitors::{
+    advance_index, after_hotplug, bounding_region, centered_region, hotplug_infos, margins_for,
+    monitor_at, start_monitor_index, uncovered_ratio, usable_region, Direction, HotplugAction,
+    MonitorInfo, MonitorMove, MonitorSummary, Region, StartContext,
+};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use overrides::{apply_import, config_changes, diff_value, merge_value, ConfigChange, ImportMode};
+use precision::{config_precision, MonitorPrecision, MonitorSize, DEFAULT_MAX_CELL_PX};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
+use session::{CloseReason, OverlaySession};
 use std::{
     collections::{HashMap, HashSet},
     fs, io,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc, Arc, Mutex,
     },
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tauri::{
     menu::Menu,
@@ -25,19 +54,10 @@ use tauri::{
 };
 use tauri::{
     AppHandle, Emitter, EventTarget, Manager, PhysicalPosition, PhysicalSize, Position, Size,
-    State, WebviewUrl, WebviewWindowBuilder,
+    State, WebviewUrl, WebviewWindow, WebviewWindowBuilder,
 };
 use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct Region {
-    x: f64,
-    y: f64,
-    width: f64,
-    height: f64,
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 enum ClickAction {
@@ -48,12 +68,44 @@ enum ClickAction {
     Drag,
 }
 
+// 整屏激活使用 layers；光标附近激活使用 local.layers；前台窗口激活优先使用 window.layers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum ActivationMode {
+    #[default]
+    Monitor,
+    Local,
+    Window,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct OverlayActivatePayload {
     region: Region,
+    // 当前显示器名称，用于筛选定点层中按显示器分组的点
+    monitor: Option<String>,
     config: AppConfig,
     click_action: ClickAction,
+    // 非空时先显示选择显示器的步骤，region 为整个虚拟桌面
+    picker: Vec<PickerMonitor>,
+    // 跨屏模式下的各显示器，region 为它们的外接矩形，屏幕之外不可选
+    areas: Vec<MonitorArea>,
+    // 同时显示所有显示器时本屏的 jumpKeys 按键
+    prefix: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MonitorArea {
+    name: Option<String>,
+    region: Region,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PickerMonitor {
+    key: String,
+    name: Option<String>,
+    region: Region,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,6 +114,8 @@ struct NativeClickPayload {
     x: f64,
     y: f64,
     button: ClickAction,
+    #[serde(default)]
+    anchor: Option<ClickAnchor>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -70,12 +124,24 @@ struct NativeKeyPayload {
     key: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OverlayClosedPayload {
+    reason: CloseReason,
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct OverlayActionPayload {
     click_action: ClickAction,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigReloadErrorPayload {
+    message: String,
+}
+
 #[derive(Debug, Clone)]
 struct NudgeRepeat {
     key: String,
@@ -85,17 +151,36 @@ struct NudgeRepeat {
 #[derive(Debug, Default, Clone)]
 struct ActivationHotkeyIds {
     trigger: Option<u32>,
+    local: Option<u32>,
+    window: Option<u32>,
 }
 
 impl ActivationHotkeyIds {
     fn from_config(config: &AppConfig) -> Self {
+        let optional_id = |value: &String| {
+            if value.trim().is_empty() {
+                None
+            } else {
+                parse_hotkey_id(value)
+            }
+        };
         Self {
             trigger: parse_hotkey_id(&config.hotkeys.activation.trigger),
+            local: optional_id(&config.hotkeys.activation.local),
+            window: optional_id(&config.hotkeys.activation.window),
         }
     }
 
-    fn is_trigger_id(&self, id: u32) -> bool {
-        self.trigger == Some(id)
+    fn mode_for_id(&self, id: u32) -> Option<ActivationMode> {
+        if self.trigger == Some(id) {
+            Some(ActivationMode::Monitor)
+        } else if self.local == Some(id) {
+            Some(ActivationMode::Local)
+        } else if self.window == Some(id) {
+            Some(ActivationMode::Window)
+        } else {
+            None
+        }
     }
 }
 
@@ -107,10 +192,27 @@ struct AppState {
     overlay_key_map: Mutex<HashMap<u32, String>>,
     overlay_active: Mutex<bool>,
     overlay_click_action: Mutex<Option<ClickAction>>,
+    overlay_mode: Mutex<ActivationMode>,
+    // 本次覆盖层实际使用的配置（已套用显示器配置），关闭时清空
+    overlay_config: Mutex<Option<AppConfig>>,
+    overlay_session: Mutex<Option<OverlaySession>>,
+    // 各覆盖层窗口最近一次的激活数据，网页加载完成后自行拉取，关闭时清空
+    overlay_payloads: Mutex<HashMap<String, OverlayActivatePayload>>,
+    // 同时显示所有显示器时按显示器序号排列的窗口标签，选中前缀键后清空
+    overlay_pool: Mutex<Vec<String>>,
+    // 接收按键事件的覆盖层窗口
+    key_target: Mutex<String>,
+    last_close_reason: Mutex<Option<CloseReason>>,
     monitor_index: Mutex<usize>,
+    // 显示器选择或跨屏时覆盖层铺满整个桌面，不属于某一块显示器
+    overlay_spanning: Mutex<bool>,
+    // 最近一次显示覆盖层的显示器名称，供 lastUsed 启动策略使用
+    last_monitor: Mutex<Option<String>>,
     nudge_repeat: Mutex<Option<NudgeRepeat>>,
     paused: Mutex<bool>,
     tray_menu_items: Mutex<Option<TrayMenuItems>>,
+    config_watcher: Mutex<Option<RecommendedWatcher>>,
+    config_reload_error: Mutex<Option<String>>,
 }
 
 type AppMenuItem = MenuItem<Wry>;
@@ -133,68 +235,90 @@ struct TrayTexts {
     pause: &'static str,
     start: &'static str,
     quit: &'static str,
+    reload_failed: &'static str,
 }
 
 const OVERRIDE_FILE_NAME: &str = "settings.override.json";
+const OVERRIDE_FILE_NAMES: [&str; 4] = [
+    OVERRIDE_FILE_NAME,
+    "settings.override.toml",
+    "settings.override.yaml",
+    "settings.override.yml",
+];
+const SCHEMA_FILE_NAME: &str = "settings.schema.json";
+const SCHEMA_KEY: &str = "$schema";
+const PRINT_SCHEMA_FLAG: &str = "--print-schema";
 const NUDGE_REPEAT_DELAY_MS: u64 = 250;
 const NUDGE_REPEAT_INTERVAL_MS: u64 = 40;
+const CONFIG_RELOAD_DEBOUNCE_MS: u64 = 300;
+// 系统没有统一的显示器变化通知，定时比对一次
+const MONITOR_POLL_MS: u64 = 2000;
+// 空闲超时与前台窗口变化的检查间隔
+const OVERLAY_WATCH_MS: u64 = 500;
+static NEXT_OVERLAY_SESSION: AtomicU64 = AtomicU64::new(1);
+// 主覆盖层窗口；同时显示所有显示器时其余窗口为 overlay-1、overlay-2……
+const OVERLAY_LABEL: &str = "overlay";
 const DEFAULT_SWITCH_ACTION_KEY: &str = "Enter";
 const DEFAULT_NEXT_MONITOR_KEY: &str = "Tab";
 const TRAY_ICON_ID: &str = "main";
+const TRAY_TOOLTIP: &str = "Clickey";
 const TRAY_MENU_SETTINGS_ID: &str = "tray-settings";
 const TRAY_MENU_TOGGLE_ID: &str = "tray-toggle-runtime";
 const TRAY_MENU_QUIT_ID: &str = "tray-quit";
 
 fn config_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|_| "unable to resolve app config directory".to_string())?;
+    // 已存在的覆盖文件决定持久化格式，默认 JSON
+    Ok(OVERRIDE_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+        .unwrap_or_else(|| dir.join(OVERRIDE_FILE_NAME)))
+}
+
+fn override_format(path: &Path) -> ConfigFormat {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(ConfigFormat::from_file_name)
+        .unwrap_or(ConfigFormat::Json)
+}
+
+fn schema_path(app: &AppHandle) -> Result<PathBuf, String> {
     app.path()
         .app_config_dir()
-        .map(|dir| dir.join(OVERRIDE_FILE_NAME))
+        .map(|dir| dir.join(SCHEMA_FILE_NAME))
         .map_err(|_| "unable to resolve app config directory".to_string())
 }
 
-fn diff_value(default: &Value, current: &Value) -> Option<Value> {
-    if default == current {
-        return None;
+fn with_schema_ref(mut overrides: Value) -> Value {
+    if let Some(object) = overrides.as_object_mut() {
+        object.insert(
+            SCHEMA_KEY.to_string(),
+            Value::from(format!("./{}", SCHEMA_FILE_NAME)),
+        );
     }
+    overrides
+}
 
-    match (default, current) {
-        (Value::Object(default_map), Value::Object(current_map)) => {
-            let mut out = Map::new();
-            for (key, current_value) in current_map {
-                if let Some(default_value) = default_map.get(key) {
-                    if let Some(next) = diff_value(default_value, current_value) {
-                        out.insert(key.clone(), next);
-                    }
-                } else {
-                    out.insert(key.clone(), current_value.clone());
-                }
-            }
-            if out.is_empty() {
-                None
-            } else {
-                Some(Value::Object(out))
-            }
-        }
-        _ => Some(current.clone()),
+fn strip_schema_ref(overrides: &mut Value) {
+    if let Some(object) = overrides.as_object_mut() {
+        object.remove(SCHEMA_KEY);
     }
 }
 
-fn merge_value(default: &Value, overrides: &Value) -> Value {
-    match (default, overrides) {
-        (Value::Object(default_map), Value::Object(override_map)) => {
-            let mut out = default_map.clone();
-            for (key, override_value) in override_map {
-                let merged = if let Some(default_value) = out.get(key) {
-                    merge_value(default_value, override_value)
-                } else {
-                    override_value.clone()
-                };
-                out.insert(key.clone(), merged);
-            }
-            Value::Object(out)
-        }
-        (_, override_leaf) => override_leaf.clone(),
+fn config_schema_json() -> Result<String, String> {
+    serde_json::to_string_pretty(&config_schema()).map_err(|e| e.to_string())
+}
+
+fn write_config_schema(app: &AppHandle) -> Result<(), String> {
+    let path = schema_path(app)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
+    fs::write(&path, config_schema_json()?).map_err(|e| e.to_string())
 }
 
 fn build_overrides(config: &AppConfig) -> Result<Value, String> {
@@ -211,54 +335,60 @@ fn resolve_config_from_overrides(overrides: &Value) -> Result<AppConfig, String>
     Ok(config)
 }
 
-fn load_config(app: &AppHandle) -> (AppConfig, bool) {
-    let path = match config_path(app) {
-        Ok(path) => path,
-        Err(err) => {
-            println!("[config] failed to resolve path: {}", err);
-            return (default_config(), true);
-        }
+fn read_overrides(path: &Path) -> Result<Option<Value>, String> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(format!("failed to read override file: {}", err)),
     };
+    let mut overrides = parse_overrides(&contents, override_format(path))?;
+    strip_schema_ref(&mut overrides);
+    Ok(Some(overrides))
+}
 
-    match fs::read_to_string(&path) {
-        Ok(contents) => match serde_json::from_str::<Value>(&contents) {
-            Ok(overrides) => {
-                if !overrides.is_object() {
-                    println!("[config] override file must be a JSON object, using default");
-                    return (default_config(), true);
-                }
-                match resolve_config_from_overrides(&overrides) {
-                    Ok(config) => (config, false),
-                    Err(err) => {
-                        println!("[config] invalid override file, using default: {}", err);
-                        (default_config(), true)
-                    }
-                }
-            }
-            Err(err) => {
-                println!("[config] invalid override file, using default: {}", err);
-                (default_config(), true)
-            }
-        },
-        Err(err) if err.kind() == io::ErrorKind::NotFound => (default_config(), false),
-        Err(err) => {
-            println!(
-                "[config] failed to read override file, using default: {}",
-                err
-            );
-            (default_config(), true)
-        }
+// 覆盖文件无效时返回错误，由调用方决定怎么回退；这里不改动文件
+fn load_config(app: &AppHandle) -> Result<AppConfig, String> {
+    match read_overrides(&config_path(app)?)? {
+        Some(overrides) => resolve_config_from_overrides(&overrides),
+        None => Ok(default_config()),
     }
 }
 
-fn persist_config(app: &AppHandle, config: &AppConfig) -> Result<(), String> {
+// 先生成要写入的内容：手写的覆盖文件无法安全改写时，在改动运行状态之前就报错
+fn override_payload(app: &AppHandle, config: &AppConfig) -> Result<(PathBuf, String), String> {
     let path = config_path(app)?;
+    let overrides = with_schema_ref(build_overrides(config)?);
+    let format = override_format(&path);
+    let existing = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.to_string()),
+    };
+    let payload = match format {
+        ConfigFormat::Json => format_overrides(&overrides, format)?,
+        // 手写的 TOML 原地修改，保留注释和排版
+        ConfigFormat::Toml if !existing.trim().is_empty() => {
+            update_toml_overrides(&existing, &overrides)
+                .map_err(|e| format!("cannot update {}: {}", path.display(), e))?
+        }
+        // YAML 重写会丢掉注释，带注释时拒绝保存
+        ConfigFormat::Yaml if yaml_has_comments(&existing) => {
+            return Err(format!(
+                "{} has comments that saving would remove; edit it by hand or remove the comments",
+                path.display()
+            ));
+        }
+        ConfigFormat::Toml | ConfigFormat::Yaml => format_overrides(&overrides, format)?,
+    };
+    Ok((path, payload))
+}
+
+fn write_overrides(app: &AppHandle, (path, payload): (PathBuf, String)) -> Result<(), String> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
-    let overrides = build_overrides(config)?;
-    let payload = serde_json::to_string_pretty(&overrides).map_err(|e| e.to_string())?;
-    fs::write(&path, payload).map_err(|e| e.to_string())
+    fs::write(&path, payload).map_err(|e| e.to_string())?;
+    write_config_schema(app)
 }
 
 fn set_state_config(state: &AppState, config: AppConfig) -> Result<(), String> {
@@ -310,12 +440,14 @@ fn tray_texts(locale: LocaleCode) -> TrayTexts {
             pause: "暂停",
             start: "启动",
             quit: "退出",
+            reload_failed: "配置重新加载失败",
         },
         LocaleCode::EnUs => TrayTexts {
             settings: "Settings",
             pause: "Pause",
             start: "Start",
             quit: "Quit",
+            reload_failed: "Config reload failed",
         },
     }
 }
@@ -337,8 +469,19 @@ fn refresh_tray(app: &AppHandle, state: &AppState) {
     let texts = tray_texts(locale_from_config(&config));
     let paused = is_paused(state);
 
+    let reload_error = state
+        .config_reload_error
+        .lock()
+        .ok()
+        .and_then(|guard| guard.clone());
+
     if let Some(tray) = app.tray_by_id(TRAY_ICON_ID) {
         let _ = tray.set_visible(config.app.tray.enabled);
+        let tooltip = match reload_error {
+            Some(err) => format!("{}\n{}: {}", TRAY_TOOLTIP, texts.reload_failed, err),
+            None => TRAY_TOOLTIP.to_string(),
+        };
+        let _ = tray.set_tooltip(Some(tooltip));
     }
 
     if let Ok(guard) = state.tray_menu_items.lock() {
@@ -364,7 +507,7 @@ fn set_paused(app: &AppHandle, state: &AppState, paused: bool) -> Result<(), Str
     }
 
     if paused {
-        hide_overlay(app, state);
+        hide_overlay(app, state, CloseReason::Paused);
         unregister_activation_hotkeys(app, state)?;
     } else {
         let config = get_state_config(state)?;
@@ -403,6 +546,41 @@ fn validate_keys(keys: &[String], expected_len: usize, label: &str) -> Result<()
     Ok(())
 }
 
+fn validate_weights(
+    weights: &Option<Vec<f64>>,
+    expected_len: u32,
+    label: &str,
+) -> Result<(), String> {
+    let Some(weights) = weights else {
+        return Ok(());
+    };
+    if weights.len() != expected_len as usize {
+        return Err(format!(
+            "{} expects {} values but got {}",
+            label,
+            expected_len,
+            weights.len()
+        ));
+    }
+    if weights
+        .iter()
+        .any(|weight| !weight.is_finite() || *weight <= 0.0)
+    {
+        return Err(format!("{} must be > 0", label));
+    }
+    Ok(())
+}
+
+fn validate_overlap(overlap_ratio: Option<f64>, label: &str) -> Result<(), String> {
+    match overlap_ratio {
+        Some(ratio) if !(0.0..=MAX_OVERLAP_RATIO).contains(&ratio) => Err(format!(
+            "{} overlapRatio must be between 0 and {}",
+            label, MAX_OVERLAP_RATIO
+        )),
+        _ => Ok(()),
+    }
+}
+
 fn validate_hotkey(value: &str, label: &str) -> Result<(), String> {
     if value.trim().is_empty() {
         return Err(format!("{} hotkey is empty", label));
@@ -418,6 +596,23 @@ fn validate_config(config: &AppConfig) -> Result<(), String> {
     }
 
     validate_hotkey(&config.hotkeys.activation.trigger, "trigger")?;
+    let local_hotkey = &config.hotkeys.activation.local;
+    if !local_hotkey.trim().is_empty() {
+        validate_hotkey(local_hotkey, "local")?;
+        if parse_hotkey_id(local_hotkey) == parse_hotkey_id(&config.hotkeys.activation.trigger) {
+            return Err("local hotkey must differ from trigger".to_string());
+        }
+    }
+    let window_hotkey = &config.hotkeys.activation.window;
+    if !window_hotkey.trim().is_empty() {
+        validate_hotkey(window_hotkey, "window")?;
+        let window_id = parse_hotkey_id(window_hotkey);
+        if window_id == parse_hotkey_id(&config.hotkeys.activation.trigger)
+            || window_id == parse_hotkey_id(local_hotkey)
+        {
+            return Err("window hotkey must differ from trigger and local".to_string());
+        }
+    }
 
     if config.hotkeys.controls.cancel.trim().is_empty() {
         return Err("cancel hotkey is empty".to_string());
@@ -515,56 +710,176 @@ fn validate_config(config: &AppConfig) -> Result<(), String> {
     if config.overlay.font.size_px == 0 {
         return Err("overlay font sizePx must be > 0".to_string());
     }
+    for (index, profile) in config.monitor.profiles.iter().enumerate() {
+        let label = format!("monitor profile {}", index);
+        if profile.monitor.trim().is_empty() {
+            return Err(format!("{} requires a monitor name or resolution", label));
+        }
+        if profile.font_size_px == Some(0) || profile.line_width_px == Some(0) {
+            return Err(format!("{} sizes must be > 0", label));
+        }
+        for (layer_index, layer) in profile.layers.iter().enumerate() {
+            validate_layer(layer, &format!("{} layer {}", label, layer_index))?;
+        }
+    }
 
     for (layer_index, layer) in config.layers.iter().enumerate() {
-        match layer {
-            Layer::Single { rows, cols, keys } => {
-                if *rows == 0 || *cols == 0 {
-                    return Err(format!("layer {} has invalid grid size", layer_index));
-                }
-                let expected_len = (*rows as usize) * (*cols as usize);
-                validate_keys(keys, expected_len, &format!("layer {}", layer_index))?;
+        validate_layer(layer, &format!("layer {}", layer_index))?;
+    }
+
+    if config.finish.fallback_above_px > 0 {
+        match &config.finish.fallback_layer {
+            Some(layer) => validate_layer(layer, "finish fallbackLayer")?,
+            None => {
+                return Err("finish fallbackAbovePx requires a fallbackLayer".to_string());
             }
-            Layer::Combo { stage0, stage1 } => {
-                if stage0.rows == 0 || stage0.cols == 0 {
-                    return Err(format!(
-                        "layer {} stage0 has invalid grid size",
-                        layer_index
-                    ));
+        }
+    }
+
+    if config
+        .monitor
+        .jump_keys
+        .iter()
+        .any(|key| key.trim().is_empty())
+    {
+        return Err("monitor jumpKeys contains empty key labels".to_string());
+    }
+    if config.monitor.picker && config.monitor.jump_keys.is_empty() {
+        return Err("monitor picker requires jumpKeys".to_string());
+    }
+    if config.monitor.show_all && config.monitor.jump_keys.is_empty() {
+        return Err("monitor showAll requires jumpKeys".to_string());
+    }
+    if config.monitor.start_strategy == StartStrategy::Named
+        && config.monitor.start_name.trim().is_empty()
+    {
+        return Err("monitor startName is required for the named start strategy".to_string());
+    }
+
+    if !config.finish.anchor_keys.is_empty() {
+        validate_keys(&config.finish.anchor_keys, 9, "finish anchorKeys")?;
+    }
+
+    if config.local.width_px == 0 || config.local.height_px == 0 {
+        return Err("local widthPx and heightPx must be > 0".to_string());
+    }
+    if config.local.layers.is_empty() {
+        return Err("local layers must not be empty".to_string());
+    }
+    for (layer_index, layer) in config.local.layers.iter().enumerate() {
+        validate_layer(layer, &format!("local layer {}", layer_index))?;
+    }
+    for (layer_index, layer) in config.window.layers.iter().enumerate() {
+        validate_layer(layer, &format!("window layer {}", layer_index))?;
+    }
+
+    if let Some(conflict) = analyze_layer_keys(config).errors.into_iter().next() {
+        return Err(conflict.message);
+    }
+
+    Ok(())
+}
+
+fn validate_layer(layer: &Layer, label: &str) -> Result<(), String> {
+    match layer {
+        Layer::Single {
+            rows,
+            cols,
+            keys,
+            row_weights,
+            col_weights,
+            overlap_ratio,
+        } => {
+            if *rows == 0 || *cols == 0 {
+                return Err(format!("{} has invalid grid size", label));
+            }
+            let expected_len = (*rows as usize) * (*cols as usize);
+            validate_keys(keys, expected_len, label)?;
+            validate_weights(row_weights, *rows, &format!("{} rowWeights", label))?;
+            validate_weights(col_weights, *cols, &format!("{} colWeights", label))?;
+            validate_overlap(*overlap_ratio, label)?;
+        }
+        Layer::Combo {
+            stages,
+            overlap_ratio,
+        } => {
+            if stages.is_empty() {
+                return Err(format!("{} has no stages", label));
+            }
+            validate_overlap(*overlap_ratio, label)?;
+            for (stage_index, stage) in stages.iter().enumerate() {
+                let label = format!("{} stage{}", label, stage_index);
+                if stage.rows == 0 || stage.cols == 0 {
+                    return Err(format!("{} has invalid grid size", label));
                 }
-                if stage1.rows == 0 || stage1.cols == 0 {
+                let expected = (stage.rows as usize) * (stage.cols as usize);
+                validate_keys(&stage.keys, expected, &label)?;
+                validate_weights(
+                    &stage.row_weights,
+                    stage.rows,
+                    &format!("{} rowWeights", label),
+                )?;
+                validate_weights(
+                    &stage.col_weights,
+                    stage.cols,
+                    &format!("{} colWeights", label),
+                )?;
+            }
+        }
+        Layer::Bisect {
+            left,
+            right,
+            up,
+            down,
+            finish,
+        } => {
+            let bisect_keys = [left, right, up, down, finish].map(String::clone);
+            validate_keys(&bisect_keys, 5, label)?;
+        }
+        Layer::Points { points } => {
+            if points.is_empty() {
+                return Err(format!("{} has no points", label));
+            }
+            let point_keys: Vec<String> = points.iter().map(|point| point.key.clone()).collect();
+            validate_keys(&point_keys, points.len(), label)?;
+            for (point_index, point) in points.iter().enumerate() {
+                let finite = point.x.is_finite() && point.y.is_finite();
+                let in_range = match point.origin {
+                    PointOrigin::Absolute => finite,
+                    PointOrigin::Monitor => finite && point.x >= 0.0 && point.y >= 0.0,
+                };
+                if !in_range {
                     return Err(format!(
-                        "layer {} stage1 has invalid grid size",
-                        layer_index
+                        "{} point {} has invalid position",
+                        label, point_index
                     ));
                 }
-                let expected0 = (stage0.rows as usize) * (stage0.cols as usize);
-                validate_keys(
-                    &stage0.keys,
-                    expected0,
-                    &format!("layer {} stage0", layer_index),
-                )?;
-                let expected1 = (stage1.rows as usize) * (stage1.cols as usize);
-                validate_keys(
-                    &stage1.keys,
-                    expected1,
-                    &format!("layer {} stage1", layer_index),
-                )?;
             }
         }
     }
-
     Ok(())
 }
 
+fn analyze_layer_keys(config: &AppConfig) -> KeyConflictReport {
+    analyze_keys(config, &|key| resolve_shortcut(key).is_some())
+}
+
 fn apply_runtime_config(
     app: &AppHandle,
     state: &AppState,
     mut config: AppConfig,
+    persist: bool,
 ) -> Result<AppConfig, String> {
     config.app.locale = locale_value(locale_from_config(&config)).to_string();
     validate_config(&config)?;
+    let payload = if persist {
+        Some(override_payload(app, &config)?)
+    } else {
+        None
+    };
     set_state_config(state, config.clone())?;
+    // 开启 showAll 时提前建好各屏的覆盖层窗口
+    ensure_overlay_pool(app, available_monitors(app).len());
     let paused = is_paused(state);
     if paused {
         unregister_activation_hotkeys(app, state)?;
@@ -576,16 +891,129 @@ fn apply_runtime_config(
         .lock()
         .map(|guard| *guard)
         .unwrap_or(false);
-    if overlay_active && !paused {
-        register_overlay_hotkeys(app, state, &config)?;
-    } else if overlay_active {
-        hide_overlay(app, state);
+    if overlay_active {
+        // 打开中的覆盖层仍按旧配置（含本地、前台窗口与显示器配置的层）运行，直接关闭
+        let reason = if paused {
+            CloseReason::Paused
+        } else {
+            CloseReason::ConfigChanged
+        };
+        hide_overlay(app, state, reason);
+    }
+    if let Some(payload) = payload {
+        write_overrides(app, payload)?;
     }
-    persist_config(app, &config)?;
+    // 边距和工作区设置会改变 usable，显示器本身没变也要刷新
+    let monitors = monitor_summaries(app, &available_monitors(app), &config.monitor);
+    let _ = app.emit("monitors:changed", &monitors);
     refresh_tray(app, state);
     Ok(config)
 }
 
+fn set_config_reload_error(state: &AppState, error: Option<String>) {
+    if let Ok(mut guard) = state.config_reload_error.lock() {
+        *guard = error;
+    }
+}
+
+fn reload_config_from_disk(app: &AppHandle, state: &AppState) {
+    let resolved = config_path(app)
+        .and_then(|path| read_overrides(&path))
+        .and_then(|overrides| {
+            // 覆盖文件被删除即回到默认配置
+            let overrides = overrides.unwrap_or_else(|| Value::Object(Map::new()));
+            resolve_config_from_overrides(&overrides)
+        });
+
+    let result = resolved.and_then(|config| {
+        let current = get_state_config(state)?;
+        let unchanged = serde_json::to_value(&current).ok() == serde_json::to_value(&config).ok();
+        if unchanged {
+            // 自己保存时写入的文件也会触发，这里无需处理
+            return Ok(None);
+        }
+        // 这里不能写回文件，否则会再次触发监听
+        apply_runtime_config(app, state, config, false).map(Some)
+    });
+
+    match result {
+        Ok(applied) => {
+            set_config_reload_error(state, None);
+            refresh_tray(app, state);
+            if let Some(config) = applied {
+                println!("[config] reloaded override file");
+                let _ = app.emit_to(
+                    EventTarget::webview_window("settings"),
+                    "config:reloaded",
+                    config,
+                );
+            }
+        }
+        Err(err) => {
+            println!("[config] reload failed, keeping current config: {}", err);
+            set_config_reload_error(state, Some(err.clone()));
+            refresh_tray(app, state);
+            let _ = app.emit_to(
+                EventTarget::webview_window("settings"),
+                "config:reload-error",
+                ConfigReloadErrorPayload { message: err },
+            );
+        }
+    }
+}
+
+fn watch_config_file(app: &AppHandle, state: &AppState) -> Result<(), String> {
+    let path = config_path(app)?;
+    let dir = path
+        .parent()
+        .ok_or_else(|| "override file has no parent directory".to_string())?
+        .to_path_buf();
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    // 监听所在目录而不是文件本身：编辑器常以替换文件的方式保存，文件级监听会失效
+    let (tx, rx) = mpsc::channel::<()>();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Ok(event) = res else {
+            return;
+        };
+        if event.kind.is_access() {
+            return;
+        }
+        let touches_override = event.paths.iter().any(|changed| {
+            changed
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| OVERRIDE_FILE_NAMES.contains(&name))
+        });
+        if touches_override {
+            let _ = tx.send(());
+        }
+    })
+    .map_err(|e| e.to_string())?;
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(|e| e.to_string())?;
+
+    if let Ok(mut guard) = state.config_watcher.lock() {
+        *guard = Some(watcher);
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        while rx.recv().is_ok() {
+            // 一次保存产生的多个事件合并为一次重新加载
+            while rx
+                .recv_timeout(Duration::from_millis(CONFIG_RELOAD_DEBOUNCE_MS))
+                .is_ok()
+            {}
+            reload_config_from_disk(&app_handle, app_handle.state::<AppState>().inner());
+        }
+    });
+
+    println!("[config] watching {}", path.display());
+    Ok(())
+}
+
 #[tauri::command]
 fn apply_config(
     app: AppHandle,
@@ -593,7 +1021,7 @@ fn apply_config(
     config: AppConfig,
 ) -> Result<(), String> {
     println!("[config] apply_config called");
-    apply_runtime_config(&app, state.inner(), config)?;
+    apply_runtime_config(&app, state.inner(), config, true)?;
     Ok(())
 }
 
@@ -604,24 +1032,240 @@ fn get_config(state: State<'_, AppState>) -> Result<AppConfig, String> {
 
 #[tauri::command]
 fn reset_config(app: AppHandle, state: State<'_, AppState>) -> Result<AppConfig, String> {
-    apply_runtime_config(&app, state.inner(), default_config())
+    apply_runtime_config(&app, state.inner(), default_config(), true)
 }
 
 #[tauri::command]
 fn set_locale(app: AppHandle, state: State<'_, AppState>, locale: String) -> Result<(), String> {
     let mut config = get_state_config(state.inner())?;
     config.app.locale = locale_value(normalize_locale(&locale)).to_string();
+    let payload = override_payload(&app, &config)?;
     set_state_config(state.inner(), config.clone())?;
-    persist_config(&app, &config)?;
+    write_overrides(&app, payload)?;
     refresh_tray(&app, state.inner());
     Ok(())
 }
 
+#[tauri::command]
+fn export_override(state: State<'_, AppState>, format: ConfigFormat) -> Result<String, String> {
+    let config = get_state_config(state.inner())?;
+    let overrides = with_schema_ref(build_overrides(&config)?);
+    format_overrides(&overrides, format)
+}
+
 #[tauri::command]
 fn export_override_json(state: State<'_, AppState>) -> Result<String, String> {
+    export_override(state, ConfigFormat::Json)
+}
+
+#[tauri::command]
+fn analyze_config(config: AppConfig) -> KeyConflictReport {
+    analyze_layer_keys(&config)
+}
+
+// 以设置页当前编辑中的配置为准，避开其控制键和切换显示器的按键
+#[tauri::command]
+fn generate_layout(
+    mut config: AppConfig,
+    request: LayoutRequest,
+) -> Result<GeneratedLayout, String> {
+    let generated = generate_layers(&request, &config, &|key| resolve_shortcut(key).is_some())?;
+    config.layers = generated.layers.clone();
+    validate_config(&config)?;
+    Ok(generated)
+}
+
+#[tauri::command]
+fn analyze_precision(
+    app: AppHandle,
+    config: AppConfig,
+    max_cell_px: Option<f64>,
+) -> Result<Vec<MonitorPrecision>, String> {
+    let max_cell_px = max_cell_px
+        .filter(|value| value.is_finite() && *value > 0.0)
+        .unwrap_or(DEFAULT_MAX_CELL_PX);
+    let monitors = app.available_monitors().map_err(|e| e.to_string())?;
+    Ok(monitors
+        .iter()
+        .enumerate()
+        .map(|(index, monitor)| {
+            // 按覆盖层实际覆盖的区域计算，扣除任务栏与边距
+            let usable = overlay_region(monitor, &config.monitor);
+            let size = MonitorSize {
+                name: monitor.name().cloned(),
+                width: usable.width as u32,
+                height: usable.height as u32,
+                scale_factor: monitor.scale_factor(),
+            };
+            let config = config.for_monitor(
+                size.name.as_deref(),
+                monitor.size().width,
+                monitor.size().height,
+            );
+            config_precision(index, &size, &config, max_cell_px)
+        })
+        .collect())
+}
+
+#[tauri::command]
+fn get_monitors(app: AppHandle, state: State<'_, AppState>) -> Result<Vec<MonitorSummary>, String> {
     let config = get_state_config(state.inner())?;
-    let overrides = build_overrides(&config)?;
-    serde_json::to_string_pretty(&overrides).map_err(|e| e.to_string())
+    let monitors = app.available_monitors().map_err(|e| e.to_string())?;
+    Ok(monitor_summaries(&app, &monitors, &config.monitor))
+}
+
+fn monitor_summaries(
+    app: &AppHandle,
+    monitors: &[tauri::Monitor],
+    config: &MonitorConfig,
+) -> Vec<MonitorSummary> {
+    let primary = primary_monitor_index(app, monitors);
+    monitors
+        .iter()
+        .enumerate()
+        .map(|(index, monitor)| MonitorSummary {
+            index,
+            name: monitor.name().cloned(),
+            full: monitor_region(monitor),
+            work_area: work_area_region(monitor),
+            usable: overlay_region(monitor, config),
+            scale_factor: monitor.scale_factor(),
+            primary: index == primary,
+        })
+        .collect()
+}
+
+// 显示器增减、排列、缩放或任务栏变化（usable 随之移动）时通知各窗口
+fn watch_monitors(app: &AppHandle) {
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        let current = |app: &AppHandle| {
+            let config = get_state_config(app.state::<AppState>().inner())
+                .unwrap_or_else(|_| default_config());
+            monitor_summaries(app, &available_monitors(app), &config.monitor)
+        };
+        let mut last = current(&app_handle);
+        loop {
+            std::thread::sleep(Duration::from_millis(MONITOR_POLL_MS));
+            let next = current(&app_handle);
+            if last == next {
+                continue;
+            }
+            println!("[monitor] monitors changed: {} monitors", next.len());
+            handle_monitor_change(&app_handle, &last, &next);
+            let _ = app_handle.emit("monitors:changed", &next);
+            last = next;
+        }
+    });
+}
+
+// 覆盖层打开时显示器被拔掉或重新排列：换到仍然存在的显示器，否则取消
+fn handle_monitor_change(app: &AppHandle, before: &[MonitorSummary], after: &[MonitorSummary]) {
+    let state = app.state::<AppState>();
+    let stored = state.monitor_index.lock().map(|guard| *guard).unwrap_or(0);
+    let action = after_hotplug(&hotplug_infos(before), &hotplug_infos(after), stored);
+    if let (HotplugAction::Keep(index) | HotplugAction::Move(index), Ok(mut guard)) =
+        (action, state.monitor_index.lock())
+    {
+        *guard = index;
+    }
+
+    let active = state
+        .overlay_active
+        .lock()
+        .map(|guard| *guard)
+        .unwrap_or(false);
+    if !active {
+        return;
+    }
+    let spanning = state
+        .overlay_spanning
+        .lock()
+        .map(|guard| *guard)
+        .unwrap_or(false);
+    match (overlay_mode(state.inner()), action) {
+        // 显示器还在但任务栏等挪动了 usable：按新区域重新显示
+        (ActivationMode::Monitor, HotplugAction::Keep(index)) if !spanning => {
+            if before.get(stored).map(|summary| &summary.usable)
+                != after.get(index).map(|summary| &summary.usable)
+            {
+                println!(
+                    "[monitor] overlay usable region changed on monitor {}",
+                    index
+                );
+                reshow_monitor_overlay(app, index);
+            }
+        }
+        (_, HotplugAction::Keep(_)) if !spanning => {}
+        (ActivationMode::Monitor, HotplugAction::Move(index)) if !spanning => {
+            println!("[monitor] overlay moved to monitor {}", index);
+            switch_monitor(app, MonitorMove::Jump(index));
+        }
+        // 跨屏区域、光标附近和前台窗口的区域都依赖原来的排列，无法换屏
+        _ => {
+            println!("[monitor] overlay cancelled by {:?}", action);
+            hide_overlay(app, state.inner(), CloseReason::MonitorsChanged);
+        }
+    }
+}
+
+#[tauri::command]
+fn get_config_schema() -> Result<String, String> {
+    config_schema_json()
+}
+
+fn resolve_import(
+    state: &AppState,
+    contents: &str,
+    file_name: Option<&str>,
+    mode: ImportMode,
+) -> Result<(AppConfig, AppConfig), String> {
+    let format = ConfigFormat::detect(file_name, contents);
+    let mut document = parse_document(contents, format)?;
+    strip_schema_ref(&mut document);
+
+    let current = get_state_config(state)?;
+    let default_value = serde_json::to_value(default_config()).map_err(|e| e.to_string())?;
+    let current_value = serde_json::to_value(&current).map_err(|e| e.to_string())?;
+    let resolved = apply_import(&default_value, &current_value, &document, mode)?;
+    let config: AppConfig = serde_json::from_value(resolved).map_err(|e| e.to_string())?;
+    validate_config(&config)?;
+    Ok((current, config))
+}
+
+#[tauri::command]
+fn preview_override_import(
+    state: State<'_, AppState>,
+    contents: String,
+    file_name: Option<String>,
+    mode: Option<ImportMode>,
+) -> Result<Vec<ConfigChange>, String> {
+    let (current, config) = resolve_import(
+        state.inner(),
+        &contents,
+        file_name.as_deref(),
+        mode.unwrap_or_default(),
+    )?;
+    let before = serde_json::to_value(&current).map_err(|e| e.to_string())?;
+    let after = serde_json::to_value(&config).map_err(|e| e.to_string())?;
+    Ok(config_changes(&before, &after))
+}
+
+#[tauri::command]
+fn import_override(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    contents: String,
+    file_name: Option<String>,
+    mode: Option<ImportMode>,
+) -> Result<AppConfig, String> {
+    let (_, config) = resolve_import(
+        state.inner(),
+        &contents,
+        file_name.as_deref(),
+        mode.unwrap_or_default(),
+    )?;
+    apply_runtime_config(&app, state.inner(), config, true)
 }
 
 #[tauri::command]
@@ -630,12 +1274,13 @@ fn import_override_json(
     state: State<'_, AppState>,
     json: String,
 ) -> Result<AppConfig, String> {
-    let overrides: Value = serde_json::from_str(&json).map_err(|e| e.to_string())?;
-    if !overrides.is_object() {
-        return Err("override JSON must be an object".to_string());
-    }
-    let config = resolve_config_from_overrides(&overrides)?;
-    apply_runtime_config(&app, state.inner(), config)
+    import_override(
+        app,
+        state,
+        json,
+        Some(OVERRIDE_FILE_NAME.to_string()),
+        Some(ImportMode::Replace),
+    )
 }
 
 #[tauri::command]
@@ -645,19 +1290,63 @@ fn native_click(app: AppHandle, payload: NativeClickPayload) -> Result<(), Strin
         payload.button, payload.x, payload.y
     );
     perform_click(&app, &payload)?;
-    hide_overlay(&app, app.state::<AppState>().inner());
+    hide_overlay(&app, app.state::<AppState>().inner(), CloseReason::Click);
 
     Ok(())
 }
 
 #[tauri::command]
-fn close_overlay(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
-    hide_overlay(&app, state.inner());
+fn close_overlay(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    reason: Option<CloseReason>,
+) -> Result<(), String> {
+    hide_overlay(&app, state.inner(), reason.unwrap_or(CloseReason::Cancel));
     Ok(())
 }
 
+#[tauri::command]
+fn get_last_close_reason(state: State<'_, AppState>) -> Result<Option<CloseReason>, String> {
+    state
+        .last_close_reason
+        .lock()
+        .map(|guard| *guard)
+        .map_err(|_| "close reason lock poisoned".to_string())
+}
+
+// 覆盖层网页挂载时拉取本窗口的激活数据，补上加载完成前发出的 overlay:activate
+#[tauri::command]
+fn get_overlay_payload(
+    window: WebviewWindow,
+    state: State<'_, AppState>,
+) -> Result<Option<OverlayActivatePayload>, String> {
+    state
+        .overlay_payloads
+        .lock()
+        .map(|guard| guard.get(window.label()).cloned())
+        .map_err(|_| "overlay payload lock poisoned".to_string())
+}
+
+// 设置页打开时读取启动或重新加载时覆盖文件的错误
+#[tauri::command]
+fn get_config_reload_error(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    state
+        .config_reload_error
+        .lock()
+        .map(|guard| guard.clone())
+        .map_err(|_| "config reload error lock poisoned".to_string())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    if std::env::args().any(|arg| arg == PRINT_SCHEMA_FLAG) {
+        match config_schema_json() {
+            Ok(schema) => println!("{}", schema),
+            Err(err) => eprintln!("[config] failed to generate schema: {}", err),
+        }
+        return;
+    }
+
     let default_cfg = default_config();
     let _ = parse_shortcut_or_panic("trigger", &default_cfg.hotkeys.activation.trigger);
     let _ = parse_shortcut_or_panic("switchAction", &default_cfg.hotkeys.controls.switch_action);
@@ -697,18 +1386,18 @@ pub fn run() {
             }
             println!("[shortcut] pressed id={}", shortcut.id());
 
-            let is_activation_trigger = {
+            let activation_mode = {
                 let ids = state
                     .activation_ids
                     .lock()
                     .map(|guard| guard.clone())
                     .unwrap_or_default();
-                ids.is_trigger_id(shortcut.id())
+                ids.mode_for_id(shortcut.id())
             };
 
-            if is_activation_trigger {
-                println!("[shortcut] activation trigger");
-                trigger_overlay(app, ClickAction::Left);
+            if let Some(mode) = activation_mode {
+                println!("[shortcut] activation trigger mode={:?}", mode);
+                trigger_overlay(app, ClickAction::Left, mode);
                 return;
             }
 
@@ -730,6 +1419,7 @@ pub fn run() {
 
             if let Some(key) = overlay_key {
                 println!("[shortcut] overlay key={}", key);
+                touch_overlay_session(state.inner());
                 let (switch_action_key, next_monitor_key) = state
                     .config
                     .lock()
@@ -750,18 +1440,27 @@ pub fn run() {
                     return;
                 }
                 if is_next_monitor_key(&key, &next_monitor_key) {
-                    switch_monitor(app);
+                    switch_monitor(app, MonitorMove::Next);
+                    return;
+                }
+                if let Some(index) = pool_jump_index(state.inner(), &key) {
+                    select_pool_overlay(app, index);
+                    return;
+                }
+                if let Some(step) = monitor_move_for_key(state.inner(), &key) {
+                    switch_monitor(app, step);
                     return;
                 }
                 if is_nudge_key(&key) && is_nudge_repeat_active(state.inner(), &key) {
                     return;
                 }
                 let _ = app.emit_to(
-                    EventTarget::webview_window("overlay"),
+                    EventTarget::webview_window(key_target(state.inner())),
                     "native:key",
                     NativeKeyPayload { key: key.clone() },
                 );
-                if is_nudge_key(&key) {
+                // 二分层的方向键每按一次只切一半，不做长按连发（后端不跟踪当前层）
+                if is_nudge_key(&key) && !is_bisect_key(state.inner(), &key) {
                     start_nudge_repeat(app.clone(), state.inner(), key);
                 }
             } else {
@@ -779,10 +1478,21 @@ pub fn run() {
             overlay_key_map: Mutex::new(HashMap::new()),
             overlay_active: Mutex::new(false),
             overlay_click_action: Mutex::new(None),
+            overlay_mode: Mutex::new(ActivationMode::Monitor),
             monitor_index: Mutex::new(0),
+            overlay_spanning: Mutex::new(false),
+            overlay_config: Mutex::new(None),
+            overlay_session: Mutex::new(None),
+            overlay_payloads: Mutex::new(HashMap::new()),
+            overlay_pool: Mutex::new(Vec::new()),
+            key_target: Mutex::new(OVERLAY_LABEL.to_string()),
+            last_close_reason: Mutex::new(None),
+            last_monitor: Mutex::new(None),
             nudge_repeat: Mutex::new(None),
             paused: Mutex::new(false),
             tray_menu_items: Mutex::new(None),
+            config_watcher: Mutex::new(None),
+            config_reload_error: Mutex::new(None),
         })
         .plugin(tauri_plugin_opener::init())
         .plugin(global_shortcut_plugin)
@@ -791,38 +1501,59 @@ pub fn run() {
             get_config,
             reset_config,
             set_locale,
+            export_override,
             export_override_json,
+            import_override,
             import_override_json,
+            preview_override_import,
+            get_config_schema,
+            analyze_config,
+            generate_layout,
+            analyze_precision,
+            get_monitors,
             native_click,
-            close_overlay
+            close_overlay,
+            get_last_close_reason,
+            get_config_reload_error,
+            get_overlay_payload
         ])
         .setup(|app| {
             let handle = app.handle();
-            create_overlay_window(&handle)?;
+            create_overlay_window(handle, OVERLAY_LABEL)?;
             let state = app.state::<AppState>();
-            let (mut config, mut should_persist) = load_config(&handle);
+            let mut config = match load_config(handle) {
+                Ok(config) => config,
+                Err(err) => {
+                    // 文件留在磁盘上，修好后热重载即可恢复；与重新加载失败一样提示
+                    println!("[config] invalid override file, using defaults: {}", err);
+                    set_config_reload_error(state.inner(), Some(err));
+                    default_config()
+                }
+            };
             if let Err(err) = set_state_config(state.inner(), config.clone()) {
                 println!("[config] failed to set state config: {}", err);
                 config = default_config();
                 let _ = set_state_config(state.inner(), config.clone());
-                should_persist = true;
             }
+            ensure_overlay_pool(handle, available_monitors(handle).len());
 
             if let Err(err) =
-                register_activation_hotkeys(&handle, app.state::<AppState>().inner(), &config)
+                register_activation_hotkeys(handle, app.state::<AppState>().inner(), &config)
             {
                 println!("[hotkeys] activation register failed: {}", err);
                 let fallback = default_config();
                 let _ = set_state_config(state.inner(), fallback.clone());
-                register_activation_hotkeys(&handle, app.state::<AppState>().inner(), &fallback)?;
-                config = fallback;
-                should_persist = true;
+                register_activation_hotkeys(handle, app.state::<AppState>().inner(), &fallback)?;
+            }
+            if let Err(err) = write_config_schema(handle) {
+                println!("[config] failed to write schema file: {}", err);
             }
-            if should_persist {
-                let _ = persist_config(&handle, &config);
+            register_tray(handle, state.inner())?;
+            refresh_tray(handle, state.inner());
+            if let Err(err) = watch_config_file(handle, state.inner()) {
+                println!("[config] failed to watch override file: {}", err);
             }
-            register_tray(&handle, state.inner())?;
-            refresh_tray(&handle, state.inner());
+            watch_monitors(handle);
             println!("[startup] activation hotkeys registered");
             Ok(())
         })
@@ -935,24 +1666,166 @@ fn show_settings(app: &AppHandle) {
     }
 }
 
-fn create_overlay_window(app: &AppHandle) -> tauri::Result<()> {
-    let overlay = WebviewWindowBuilder::new(app, "overlay", WebviewUrl::App("overlay".into()))
-        .transparent(true)
-        .decorations(false)
-        .resizable(false)
-        .always_on_top(true)
-        .skip_taskbar(true)
-        .visible(false)
-        .build()?;
+fn create_overlay_window(app: &AppHandle, label: &str) -> tauri::Result<()> {
+    let overlay = WebviewWindowBuilder::new(app, label, WebviewUrl::App("overlay".into()))
+        .transparent(true)
+        .decorations(false)
+        .resizable(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .visible(false)
+        .build()?;
+
+    overlay.set_ignore_cursor_events(true)?;
+    overlay.set_focusable(false)?;
+
+    Ok(())
+}
+
+fn overlay_label(slot: usize) -> String {
+    match slot {
+        0 => OVERLAY_LABEL.to_string(),
+        _ => format!("{}-{}", OVERLAY_LABEL, slot),
+    }
+}
+
+fn is_overlay_label(label: &str) -> bool {
+    label == OVERLAY_LABEL
+        || label
+            .strip_prefix(OVERLAY_LABEL)
+            .and_then(|rest| rest.strip_prefix('-'))
+            .is_some_and(|slot| slot.parse::<usize>().is_ok())
+}
+
+// 同时显示所有显示器时每块屏一个覆盖层窗口，已有的窗口不再重建
+fn ensure_overlay_pool(app: &AppHandle, monitor_count: usize) {
+    let show_all = get_state_config(app.state::<AppState>().inner())
+        .map(|config| config.monitor.show_all)
+        .unwrap_or(false);
+    if !show_all {
+        return;
+    }
+    for slot in 1..monitor_count {
+        let label = overlay_label(slot);
+        if app.get_webview_window(&label).is_some() {
+            continue;
+        }
+        match create_overlay_window(app, &label) {
+            Ok(()) => println!("[overlay] created {}", label),
+            Err(err) => println!("[overlay] failed to create {}: {}", label, err),
+        }
+    }
+}
+
+fn emit_to_overlays<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: S) {
+    for label in app.webview_windows().into_keys() {
+        if is_overlay_label(&label) {
+            let _ = app.emit_to(EventTarget::webview_window(label), event, payload.clone());
+        }
+    }
+}
+
+// 只保留主覆盖层
+fn hide_overlay_pool(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    if let Ok(mut pool) = state.overlay_pool.lock() {
+        pool.clear();
+    }
+    if let Ok(mut payloads) = state.overlay_payloads.lock() {
+        payloads.retain(|label, _| label == OVERLAY_LABEL);
+    }
+    for (label, window) in app.webview_windows() {
+        if label != OVERLAY_LABEL && is_overlay_label(&label) {
+            let _ = window.hide();
+        }
+    }
+}
+
+// 先记下激活数据再发事件：刚建好的窗口网页还没加载完收不到事件，挂载后会自行拉取
+fn activate_overlay(app: &AppHandle, label: &str, payload: OverlayActivatePayload) {
+    if let Ok(mut payloads) = app.state::<AppState>().overlay_payloads.lock() {
+        payloads.insert(label.to_string(), payload.clone());
+    }
+    let _ = app.emit_to(
+        EventTarget::webview_window(label),
+        "overlay:activate",
+        payload,
+    );
+}
+
+fn key_target(state: &AppState) -> String {
+    state
+        .key_target
+        .lock()
+        .map(|guard| guard.clone())
+        .unwrap_or_else(|_| OVERLAY_LABEL.to_string())
+}
+
+fn set_key_target(state: &AppState, label: &str) {
+    if let Ok(mut guard) = state.key_target.lock() {
+        *guard = label.to_string();
+    }
+}
+
+// 同时显示所有显示器时按下的是哪块屏的前缀键
+fn pool_jump_index(state: &AppState, key: &str) -> Option<usize> {
+    let pool_len = state.overlay_pool.lock().ok()?.len();
+    let jump_keys = state.config.lock().ok()?.monitor.jump_keys.clone();
+    jump_keys
+        .iter()
+        .position(|configured| {
+            !configured.trim().is_empty() && key.eq_ignore_ascii_case(configured)
+        })
+        .filter(|index| *index < pool_len)
+}
 
-    overlay.set_ignore_cursor_events(true)?;
-    overlay.set_focusable(false)?;
+// 选中前缀键所在的覆盖层：其余窗口隐藏，之后的按键都发给它，前缀键加格子键即一次选择
+fn select_pool_overlay(app: &AppHandle, index: usize) {
+    let state = app.state::<AppState>();
+    let labels = state
+        .overlay_pool
+        .lock()
+        .map(|mut guard| std::mem::take(&mut *guard))
+        .unwrap_or_default();
+    let Some(label) = labels.get(index).cloned() else {
+        return;
+    };
+    for other in labels.iter().filter(|other| **other != label) {
+        if let Some(window) = app.get_webview_window(other) {
+            let _ = window.hide();
+        }
+    }
 
-    Ok(())
+    let monitors = available_monitors(app);
+    let monitor = monitors.get(index);
+    let config = overlay_config(state.inner(), monitor);
+    set_active_overlay_config(state.inner(), &config);
+    if let Ok(mut guard) = state.monitor_index.lock() {
+        *guard = index;
+    }
+    remember_monitor(
+        state.inner(),
+        &monitor.and_then(|monitor| monitor.name().cloned()),
+    );
+    if let Ok(mut spanning) = state.overlay_spanning.lock() {
+        *spanning = false;
+    }
+    set_key_target(state.inner(), &label);
+    let _ = app.emit_to(EventTarget::webview_window(&label), "overlay:selected", ());
+    println!("[overlay] selected monitor {} on {}", index, label);
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        if let Err(err) =
+            register_overlay_hotkeys(&app_handle, app_handle.state::<AppState>().inner(), &config)
+        {
+            println!("[hotkeys] overlay register failed: {}", err);
+        }
+    });
 }
 
-fn show_overlay_window(app: &AppHandle, region: &Region) {
-    if let Some(window) = app.get_webview_window("overlay") {
+fn show_overlay_window(app: &AppHandle, label: &str, region: &Region) {
+    if let Some(window) = app.get_webview_window(label) {
         let target_pos = PhysicalPosition::new(region.x as i32, region.y as i32);
         let target_size =
             PhysicalSize::new(region.width.max(1.0) as u32, region.height.max(1.0) as u32);
@@ -987,6 +1860,22 @@ fn show_overlay_window(app: &AppHandle, region: &Region) {
                 println!("[overlay] adjusted size by ({}, {})", border_w, border_h);
             }
         }
+
+        // 窗口跨越缩放比例不同的显示器时，系统会按新的 DPI 重新缩放窗口，需按物理像素再设置一次
+        if let (Ok(outer_size), Ok(inner_size)) = (window.outer_size(), window.inner_size()) {
+            if inner_size != target_size {
+                let border_w = outer_size.width.saturating_sub(inner_size.width);
+                let border_h = outer_size.height.saturating_sub(inner_size.height);
+                let _ = window.set_size(Size::Physical(PhysicalSize::new(
+                    target_size.width + border_w,
+                    target_size.height + border_h,
+                )));
+                println!(
+                    "[overlay] rescaled to {}x{}, restored {}x{}",
+                    inner_size.width, inner_size.height, target_size.width, target_size.height
+                );
+            }
+        }
     }
 }
 
@@ -1005,6 +1894,26 @@ fn monitor_region(monitor: &tauri::Monitor) -> Region {
     }
 }
 
+fn work_area_region(monitor: &tauri::Monitor) -> Region {
+    let area = monitor.work_area();
+    Region {
+        x: area.position.x as f64,
+        y: area.position.y as f64,
+        width: area.size.width.max(1) as f64,
+        height: area.size.height.max(1) as f64,
+    }
+}
+
+// 覆盖层在这块屏上实际覆盖的区域：按配置去掉任务栏与边距
+fn overlay_region(monitor: &tauri::Monitor, config: &MonitorConfig) -> Region {
+    let work_area = config.use_work_area.then(|| work_area_region(monitor));
+    usable_region(
+        &monitor_region(monitor),
+        work_area.as_ref(),
+        margins_for(&config.margins, monitor.name().map(String::as_str)),
+    )
+}
+
 fn primary_monitor_index(app: &AppHandle, monitors: &[tauri::Monitor]) -> usize {
     if let Ok(Some(primary)) = app.primary_monitor() {
         let primary_pos = primary.position();
@@ -1026,43 +1935,115 @@ fn primary_monitor_index(app: &AppHandle, monitors: &[tauri::Monitor]) -> usize
 fn set_start_monitor_index(
     app: &AppHandle,
     state: &AppState,
+    config: &AppConfig,
     monitors: &[tauri::Monitor],
+    windows: &SystemWindows,
 ) -> usize {
     if monitors.is_empty() {
         return 0;
     }
-    let index = primary_monitor_index(app, monitors);
+    let primary = primary_monitor_index(app, monitors);
+    let infos: Vec<MonitorInfo> = monitors
+        .iter()
+        .enumerate()
+        .map(|(index, monitor)| MonitorInfo {
+            name: monitor.name().cloned(),
+            region: monitor_region(monitor),
+            primary: index == primary,
+        })
+        .collect();
+
+    // 只读取所选策略需要的信息
+    let strategy = config.monitor.start_strategy;
+    let last_used = match strategy {
+        StartStrategy::LastUsed => state
+            .last_monitor
+            .lock()
+            .ok()
+            .and_then(|guard| guard.clone()),
+        _ => None,
+    };
+    let context = StartContext {
+        cursor: (strategy == StartStrategy::Cursor).then(|| {
+            let (x, y) = Enigo::new().mouse_location();
+            (x as f64, y as f64)
+        }),
+        focused_window: match strategy {
+            StartStrategy::FocusedWindow => windows.focused_window(),
+            _ => None,
+        },
+        last_used: last_used.as_deref(),
+    };
+    let index = start_monitor_index(&infos, strategy, &config.monitor.start_name, &context);
+    println!("[monitor] start strategy={:?} index={}", strategy, index);
+
     if let Ok(mut guard) = state.monitor_index.lock() {
         *guard = index;
     }
     index
 }
 
-fn next_monitor_region(app: &AppHandle, state: &AppState) -> Region {
+fn remember_monitor(state: &AppState, monitor: &Option<String>) {
+    if monitor.is_none() {
+        return;
+    }
+    if let Ok(mut guard) = state.last_monitor.lock() {
+        guard.clone_from(monitor);
+    }
+}
+
+fn next_monitor_region(
+    app: &AppHandle,
+    state: &AppState,
+    config: &MonitorConfig,
+    step: MonitorMove,
+) -> Option<(Region, Option<tauri::Monitor>)> {
     let monitors = available_monitors(app);
     if monitors.is_empty() {
-        return compute_virtual_region(app);
+        return Some((compute_virtual_region(app), None));
     }
 
-    let next_index = if let Ok(mut guard) = state.monitor_index.lock() {
-        let next = (*guard + 1) % monitors.len();
-        *guard = next;
-        next
-    } else {
-        0
+    let regions: Vec<Region> = monitors.iter().map(monitor_region).collect();
+    let mut guard = state.monitor_index.lock().ok()?;
+    let next_index = advance_index(&mut guard, &regions, step)?;
+
+    let monitor = &monitors[next_index];
+    Some((overlay_region(monitor, config), Some(monitor.clone())))
+}
+
+fn switch_monitor(app: &AppHandle, step: MonitorMove) {
+    let state = app.state::<AppState>();
+    if overlay_mode(state.inner()) != ActivationMode::Monitor {
+        // 光标附近和前台窗口的区域不跟随切换显示器
+        return;
+    }
+    let config = overlay_config(state.inner(), None);
+    let Some((region, target)) = next_monitor_region(app, state.inner(), &config.monitor, step)
+    else {
+        println!("[overlay] no monitor for {:?}", step);
+        return;
     };
+    show_monitor_overlay(app, region, target);
+}
 
-    monitor_region(&monitors[next_index])
+// 显示器没变、只是可用区域变了时原地重新显示
+fn reshow_monitor_overlay(app: &AppHandle, index: usize) {
+    let state = app.state::<AppState>();
+    let monitors = available_monitors(app);
+    let Some(monitor) = monitors.get(index) else {
+        return;
+    };
+    let config = overlay_config(state.inner(), None);
+    let region = overlay_region(monitor, &config.monitor);
+    show_monitor_overlay(app, region, Some(monitor.clone()));
 }
 
-fn switch_monitor(app: &AppHandle) {
+fn show_monitor_overlay(app: &AppHandle, region: Region, target: Option<tauri::Monitor>) {
     let state = app.state::<AppState>();
-    let region = next_monitor_region(app, state.inner());
-    let config = state
-        .config
-        .lock()
-        .map(|guard| guard.clone())
-        .unwrap_or_else(|_| default_config());
+    // 新显示器可能有自己的层和字号，按键也随之重新注册
+    let config = overlay_config(state.inner(), target.as_ref());
+    let monitor = target.and_then(|monitor| monitor.name().cloned());
+    set_active_overlay_config(state.inner(), &config);
     let action = state
         .overlay_click_action
         .lock()
@@ -1075,76 +2056,337 @@ fn switch_monitor(app: &AppHandle) {
         region.x, region.y, region.width, region.height
     );
 
-    show_overlay_window(app, &region);
+    show_overlay_window(app, OVERLAY_LABEL, &region);
+    hide_overlay_pool(app);
+    set_key_target(state.inner(), OVERLAY_LABEL);
+    remember_monitor(state.inner(), &monitor);
+    if let Ok(mut spanning) = state.overlay_spanning.lock() {
+        *spanning = false;
+    }
 
+    let config_for_keys = config.clone();
     let payload = OverlayActivatePayload {
         region,
+        monitor,
         config,
         click_action: action,
+        picker: Vec::new(),
+        areas: Vec::new(),
+        prefix: None,
     };
-    let _ = app.emit_to(
-        EventTarget::webview_window("overlay"),
-        "overlay:activate",
-        payload,
-    );
+    activate_overlay(app, OVERLAY_LABEL, payload);
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        if let Err(err) = register_overlay_hotkeys(
+            &app_handle,
+            app_handle.state::<AppState>().inner(),
+            &config_for_keys,
+        ) {
+            println!("[hotkeys] overlay register failed: {}", err);
+        }
+    });
 }
 
-fn trigger_overlay(app: &AppHandle, action: ClickAction) {
-    let state = app.state::<AppState>();
-    let config = state
+fn overlay_mode(state: &AppState) -> ActivationMode {
+    state
+        .overlay_mode
+        .lock()
+        .map(|guard| *guard)
+        .unwrap_or_default()
+}
+
+// 覆盖层实际使用的配置：先套用显示器配置，光标附近模式再换成 local.layers，
+// 前台窗口模式再换成 window.layers
+fn overlay_config(state: &AppState, monitor: Option<&tauri::Monitor>) -> AppConfig {
+    let mut config = state
         .config
         .lock()
         .map(|guard| guard.clone())
         .unwrap_or_else(|_| default_config());
+    if let Some(monitor) = monitor {
+        let size = monitor.size();
+        config = config.for_monitor(monitor.name().map(String::as_str), size.width, size.height);
+    }
+    match overlay_mode(state) {
+        ActivationMode::Monitor => config,
+        ActivationMode::Local => config.for_local(),
+        ActivationMode::Window => config.for_window(),
+    }
+}
+
+fn set_active_overlay_config(state: &AppState, config: &AppConfig) {
+    if let Ok(mut guard) = state.overlay_config.lock() {
+        *guard = Some(config.clone());
+    }
+}
+
+// 覆盖层打开期间按实际显示的配置处理按键
+fn active_overlay_config(state: &AppState) -> AppConfig {
+    state
+        .overlay_config
+        .lock()
+        .ok()
+        .and_then(|guard| guard.clone())
+        .unwrap_or_else(|| overlay_config(state, None))
+}
+
+fn local_region(monitors: &[tauri::Monitor], config: &AppConfig) -> Option<(usize, Region)> {
+    let (cursor_x, cursor_y) = Enigo::new().mouse_location();
+    let cursor = (cursor_x as f64, cursor_y as f64);
+    let regions: Vec<Region> = monitors.iter().map(monitor_region).collect();
+    let index = monitor_at(&regions, cursor)?;
+    let region = centered_region(
+        cursor,
+        config.local.width_px as f64,
+        config.local.height_px as f64,
+        &overlay_region(&monitors[index], &config.monitor),
+    );
+    Some((index, region))
+}
+
+// 多于一块显示器时，按 jumpKeys 给每块屏标上按键
+fn picker_monitors(monitors: &[tauri::Monitor], config: &AppConfig) -> Vec<PickerMonitor> {
+    if !config.monitor.picker || monitors.len() < 2 {
+        return Vec::new();
+    }
+    monitors
+        .iter()
+        .zip(&config.monitor.jump_keys)
+        .map(|(monitor, key)| PickerMonitor {
+            key: key.clone(),
+            name: monitor.name().cloned(),
+            region: monitor_region(monitor),
+        })
+        .collect()
+}
+
+fn span_areas(monitors: &[tauri::Monitor], config: &AppConfig) -> Vec<MonitorArea> {
+    if !config.monitor.span_all || monitors.len() < 2 {
+        return Vec::new();
+    }
+    monitors
+        .iter()
+        .map(|monitor| MonitorArea {
+            name: monitor.name().cloned(),
+            region: overlay_region(monitor, &config.monitor),
+        })
+        .collect()
+}
+
+fn trigger_overlay(app: &AppHandle, action: ClickAction, mode: ActivationMode) {
+    let state = app.state::<AppState>();
+    if let Ok(mut guard) = state.overlay_mode.lock() {
+        *guard = mode;
+    }
+    let base = overlay_config(state.inner(), None);
     let monitors = available_monitors(app);
-    let index = set_start_monitor_index(app, state.inner(), &monitors);
-    let region = if monitors.is_empty() {
-        compute_virtual_region(app)
-    } else {
-        monitor_region(&monitors[index])
+    // 起始显示器、前台窗口区域和会话监视共用这一次激活的窗口系统连接
+    let windows = SystemWindows::connect();
+    let index = set_start_monitor_index(app, state.inner(), &base, &monitors, &windows);
+    // 取不到前台窗口时退回起始显示器
+    let local = match mode {
+        ActivationMode::Monitor => None,
+        ActivationMode::Local => local_region(&monitors, &base),
+        ActivationMode::Window => {
+            let regions: Vec<Region> = monitors.iter().map(monitor_region).collect();
+            focused_region(&windows, &regions)
+        }
     };
+    // 同时显示所有显示器时不再需要选择显示器的步骤和跨屏区域
+    let show_all = mode == ActivationMode::Monitor && base.monitor.show_all && monitors.len() > 1;
+    let picker = match mode {
+        ActivationMode::Monitor if !show_all => picker_monitors(&monitors, &base),
+        _ => Vec::new(),
+    };
+    // 选择显示器的步骤优先，选中后再进入单屏
+    let areas = match mode {
+        ActivationMode::Monitor if !show_all && picker.is_empty() => span_areas(&monitors, &base),
+        _ => Vec::new(),
+    };
+    // 跨屏和选择显示器时按起始显示器选取显示器配置
+    let profile_index = local
+        .as_ref()
+        .map_or(index, |(local_index, _)| *local_index);
+    let (region, monitor) = match (local, monitors.get(index)) {
+        _ if !picker.is_empty() || !areas.is_empty() => (compute_virtual_region(app), None),
+        (Some((local_index, region)), _) => {
+            // 热插拔时按覆盖层实际所在的显示器判断
+            if let Ok(mut guard) = state.monitor_index.lock() {
+                *guard = local_index;
+            }
+            (
+                region,
+                monitors
+                    .get(local_index)
+                    .and_then(|monitor| monitor.name().cloned()),
+            )
+        }
+        (None, Some(current)) => (
+            overlay_region(current, &base.monitor),
+            current.name().cloned(),
+        ),
+        (None, None) => (compute_virtual_region(app), None),
+    };
+
+    let config = overlay_config(state.inner(), monitors.get(profile_index));
+    set_active_overlay_config(state.inner(), &config);
 
     if let Ok(mut active) = state.overlay_active.lock() {
         *active = true;
     }
+    if let Ok(mut spanning) = state.overlay_spanning.lock() {
+        *spanning = show_all || !picker.is_empty() || !areas.is_empty();
+    }
+    start_overlay_session(app, state.inner(), windows);
     if let Ok(mut stored_action) = state.overlay_click_action.lock() {
         *stored_action = Some(action.clone());
     }
     println!(
-        "[overlay] show action={:?} region=({}, {}, {}, {})",
-        action, region.x, region.y, region.width, region.height
+        "[overlay] show action={:?} mode={:?} region=({}, {}, {}, {})",
+        action, mode, region.x, region.y, region.width, region.height
     );
+    if !areas.is_empty() {
+        let regions: Vec<Region> = areas.iter().map(|area| area.region.clone()).collect();
+        println!(
+            "[overlay] span {} monitors, {:.0}% off-screen",
+            areas.len(),
+            uncovered_ratio(&regions, &region) * 100.0
+        );
+    }
+
+    show_overlay_window(app, OVERLAY_LABEL, &region);
+    set_key_target(state.inner(), OVERLAY_LABEL);
+    remember_monitor(state.inner(), &monitor);
+    if show_all {
+        show_overlay_pool(app, &monitors, index, &action);
+    } else {
+        hide_overlay_pool(app);
+    }
 
-    show_overlay_window(app, &region);
-
-    let config_for_keys = config.clone();
+    // 同时显示所有显示器时先占用各屏层的全部按键，按下前缀键后直接输入不会漏键
+    let keys = if show_all {
+        monitors
+            .iter()
+            .flat_map(|monitor| collect_overlay_keys(&overlay_config(state.inner(), Some(monitor))))
+            .collect()
+    } else {
+        collect_overlay_keys(&config)
+    };
+    let prefix = show_all
+        .then(|| config.monitor.jump_keys.get(index).cloned())
+        .flatten();
     let payload = OverlayActivatePayload {
         region,
+        monitor,
         config,
         click_action: action,
+        picker,
+        areas,
+        prefix,
     };
-
-    let _ = app.emit_to(
-        EventTarget::webview_window("overlay"),
-        "overlay:activate",
-        payload,
-    );
+    activate_overlay(app, OVERLAY_LABEL, payload);
 
     let app_handle = app.clone();
     std::thread::spawn(move || {
-        if let Err(err) = register_overlay_hotkeys(
-            &app_handle,
-            app_handle.state::<AppState>().inner(),
-            &config_for_keys,
-        ) {
+        if let Err(err) =
+            register_overlay_keys(&app_handle, app_handle.state::<AppState>().inner(), keys)
+        {
             println!("[hotkeys] overlay register failed: {}", err);
         }
     });
 }
 
-fn hide_overlay(app: &AppHandle, state: &AppState) {
-    if let Some(window) = app.get_webview_window("overlay") {
-        let _ = window.hide();
+// 起始显示器用主覆盖层，其余显示器各占一个窗口；选中前缀键后只留下主覆盖层
+fn show_overlay_pool(
+    app: &AppHandle,
+    monitors: &[tauri::Monitor],
+    start: usize,
+    action: &ClickAction,
+) {
+    ensure_overlay_pool(app, monitors.len());
+    let state = app.state::<AppState>();
+    let mut labels = vec![OVERLAY_LABEL.to_string(); monitors.len()];
+    let others = (0..monitors.len()).filter(|index| *index != start);
+    for (slot, index) in others.enumerate() {
+        labels[index] = overlay_label(slot + 1);
+    }
+    if let Ok(mut pool) = state.overlay_pool.lock() {
+        *pool = labels.clone();
+    }
+
+    for (index, label) in labels.iter().enumerate() {
+        if index == start {
+            continue;
+        }
+        let monitor = &monitors[index];
+        let config = overlay_config(state.inner(), Some(monitor));
+        let region = overlay_region(monitor, &config.monitor);
+        let prefix = config.monitor.jump_keys.get(index).cloned();
+        show_overlay_window(app, label, &region);
+        let payload = OverlayActivatePayload {
+            region,
+            monitor: monitor.name().cloned(),
+            config,
+            click_action: action.clone(),
+            picker: Vec::new(),
+            areas: Vec::new(),
+            prefix,
+        };
+        activate_overlay(app, label, payload);
+    }
+}
+
+// 覆盖层打开期间定时检查空闲超时与前台窗口变化；新会话开始或覆盖层关闭后退出。
+// 监视线程沿用激活时建立的窗口系统连接，不再每次重连
+fn start_overlay_session(app: &AppHandle, state: &AppState, windows: SystemWindows) {
+    let id = NEXT_OVERLAY_SESSION.fetch_add(1, Ordering::SeqCst);
+    let session = OverlaySession::new(id, windows.focused_handle(), Instant::now());
+    if let Ok(mut guard) = state.overlay_session.lock() {
+        *guard = Some(session);
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_millis(OVERLAY_WATCH_MS));
+        let state = app_handle.state::<AppState>();
+        let session = state
+            .overlay_session
+            .lock()
+            .ok()
+            .and_then(|guard| guard.clone());
+        let Some(session) = session.filter(|session| session.id == id) else {
+            break;
+        };
+        let overlay = get_state_config(state.inner())
+            .map(|config| config.overlay)
+            .unwrap_or_else(|_| default_config().overlay);
+        let focus = overlay
+            .cancel_on_focus_change
+            .then(|| windows.focused_handle())
+            .flatten();
+        let idle_timeout = Duration::from_millis(overlay.idle_timeout_ms as u64);
+        if let Some(reason) = session.check(Instant::now(), idle_timeout, focus) {
+            hide_overlay(&app_handle, state.inner(), reason);
+            break;
+        }
+    });
+}
+
+// 每个覆盖层按键都重新计算空闲时间
+fn touch_overlay_session(state: &AppState) {
+    if let Ok(mut guard) = state.overlay_session.lock() {
+        if let Some(session) = guard.as_mut() {
+            session.touch(Instant::now());
+        }
+    }
+}
+
+fn hide_overlay(app: &AppHandle, state: &AppState, reason: CloseReason) {
+    for (label, window) in app.webview_windows() {
+        if is_overlay_label(&label) {
+            let _ = window.hide();
+        }
     }
     let _ = unregister_overlay_hotkeys(app, state);
     stop_nudge_repeat(state);
@@ -1154,7 +2396,25 @@ fn hide_overlay(app: &AppHandle, state: &AppState) {
     if let Ok(mut action) = state.overlay_click_action.lock() {
         *action = None;
     }
-    println!("[overlay] hidden");
+    if let Ok(mut config) = state.overlay_config.lock() {
+        *config = None;
+    }
+    if let Ok(mut session) = state.overlay_session.lock() {
+        *session = None;
+    }
+    if let Ok(mut pool) = state.overlay_pool.lock() {
+        pool.clear();
+    }
+    if let Ok(mut payloads) = state.overlay_payloads.lock() {
+        payloads.clear();
+    }
+    set_key_target(state, OVERLAY_LABEL);
+    if let Ok(mut last) = state.last_close_reason.lock() {
+        *last = Some(reason);
+    }
+    // 后端主动关闭时覆盖层网页也需要清空状态
+    emit_to_overlays(app, "overlay:closed", OverlayClosedPayload { reason });
+    println!("[overlay] hidden reason={:?}", reason);
 }
 
 fn perform_click(app: &AppHandle, payload: &NativeClickPayload) -> Result<(), String> {
@@ -1164,11 +2424,17 @@ fn perform_click(app: &AppHandle, payload: &NativeClickPayload) -> Result<(), St
     let mut enigo = Enigo::new();
     let base_x = payload.x.round() as i32;
     let base_y = payload.y.round() as i32;
-    let (target_x, target_y) =
-        resolve_landing_point(base_x, base_y, &payload.button, &mouse_cfg);
+    let (target_x, target_y) = resolve_landing_point(
+        base_x,
+        base_y,
+        &payload.button,
+        payload.anchor.as_ref(),
+        &mouse_cfg,
+    );
     println!(
-        "[native] landing action={:?} x={} y={} offset_x={} offset_y={}",
+        "[native] landing action={:?} anchor={:?} x={} y={} offset_x={} offset_y={}",
         payload.button,
+        payload.anchor.map(|anchor| anchor.position),
         target_x,
         target_y,
         target_x - base_x,
@@ -1198,6 +2464,7 @@ fn resolve_landing_point(
     base_x: i32,
     base_y: i32,
     button: &ClickAction,
+    anchor: Option<&ClickAnchor>,
     cfg: &MouseConfig,
 ) -> (i32, i32) {
     if !matches!(
@@ -1212,13 +2479,12 @@ fn resolve_landing_point(
         return (base_x, base_y);
     }
 
-    // Randomize to a centered square: offsets in [-radius, radius].
+    // Randomize within the square [-radius, radius], narrowed to the anchored cell.
+    let bounds = landing_bounds(anchor, (base_x, base_y), radius);
     let mut rng = FastRng::new(seed_for_landing(base_x, base_y));
-    let offset_x = rng.range_i32_inclusive(-radius, radius);
-    let offset_y = rng.range_i32_inclusive(-radius, radius);
     (
-        base_x.saturating_add(offset_x),
-        base_y.saturating_add(offset_y),
+        rng.range_i32_inclusive(bounds.min_x, bounds.max_x),
+        rng.range_i32_inclusive(bounds.min_y, bounds.max_y),
     )
 }
 
@@ -1249,7 +2515,7 @@ fn move_mouse_to_target(enigo: &mut Enigo, target_x: i32, target_y: i32, cfg: &M
     let move_duration_ms = ((cfg.move_duration_ms.max(1) as f64) * duration_scale)
         .round()
         .clamp(18.0, 1400.0) as u64;
-    let time_steps = (move_duration_ms + base_step_ms - 1) / base_step_ms;
+    let time_steps = move_duration_ms.div_ceil(base_step_ms);
     let adaptive_stride_px = (cfg.adaptive_stride_base_px
         + distance * cfg.adaptive_stride_distance_ratio.max(0.0))
     .clamp(
@@ -1432,53 +2698,58 @@ fn click_mouse_button(enigo: &mut Enigo, button: MouseButton, press_duration_ms:
 
 fn compute_virtual_region(app: &AppHandle) -> Region {
     let monitors = app.available_monitors().unwrap_or_default();
-    if monitors.is_empty() {
-        // 无法获取屏幕信息时的保底区域
-        return Region {
-            x: 0.0,
-            y: 0.0,
-            width: 1920.0,
-            height: 1080.0,
-        };
-    }
-
-    let mut min_x = i32::MAX;
-    let mut min_y = i32::MAX;
-    let mut max_x = i32::MIN;
-    let mut max_y = i32::MIN;
-
-    for monitor in monitors {
-        let pos = monitor.position();
-        let size = monitor.size();
-        min_x = min_x.min(pos.x);
-        min_y = min_y.min(pos.y);
-        max_x = max_x.max(pos.x + size.width as i32);
-        max_y = max_y.max(pos.y + size.height as i32);
-    }
-
-    Region {
-        x: min_x as f64,
-        y: min_y as f64,
-        width: (max_x - min_x).max(1) as f64,
-        height: (max_y - min_y).max(1) as f64,
-    }
+    let regions: Vec<Region> = monitors.iter().map(monitor_region).collect();
+    // 无法获取屏幕信息时的保底区域
+    bounding_region(&regions).unwrap_or(Region {
+        x: 0.0,
+        y: 0.0,
+        width: 1920.0,
+        height: 1080.0,
+    })
 }
 
 fn collect_overlay_keys(config: &AppConfig) -> Vec<String> {
     let mut keys = Vec::new();
-    for layer in &config.layers {
+    for layer in config.layers.iter().chain(&config.finish.fallback_layer) {
         match layer {
             Layer::Single {
                 keys: layer_keys, ..
             } => {
                 keys.extend(layer_keys.iter().cloned());
             }
-            Layer::Combo { stage0, stage1 } => {
-                keys.extend(stage0.keys.iter().cloned());
-                keys.extend(stage1.keys.iter().cloned());
+            Layer::Combo { stages, .. } => {
+                for stage in stages {
+                    keys.extend(stage.keys.iter().cloned());
+                }
+            }
+            Layer::Bisect {
+                left,
+                right,
+                up,
+                down,
+                finish,
+            } => {
+                keys.extend([left, right, up, down, finish].map(String::clone));
+            }
+            Layer::Points { points } => {
+                keys.extend(points.iter().map(|point| point.key.clone()));
             }
         }
     }
+    keys.extend(config.finish.anchor_keys.iter().cloned());
+    let monitor = &config.monitor;
+    keys.push(monitor.previous_key.clone());
+    keys.extend(monitor.jump_keys.iter().cloned());
+    let directions = &monitor.direction_keys;
+    keys.extend(
+        [
+            &directions.left,
+            &directions.right,
+            &directions.up,
+            &directions.down,
+        ]
+        .map(String::clone),
+    );
 
     keys.push(config.hotkeys.controls.cancel.clone());
     keys.push(config.hotkeys.controls.undo.clone());
@@ -1505,7 +2776,18 @@ fn register_activation_hotkeys(
 ) -> Result<(), String> {
     let shortcut = parse_shortcut(&config.hotkeys.activation.trigger)
         .ok_or_else(|| "activation trigger is invalid".to_string())?;
-    let shortcuts = vec![shortcut];
+    let mut shortcuts = vec![shortcut];
+    let local = &config.hotkeys.activation.local;
+    if !local.trim().is_empty() {
+        shortcuts
+            .push(parse_shortcut(local).ok_or_else(|| "activation local is invalid".to_string())?);
+    }
+    let window = &config.hotkeys.activation.window;
+    if !window.trim().is_empty() {
+        shortcuts.push(
+            parse_shortcut(window).ok_or_else(|| "activation window is invalid".to_string())?,
+        );
+    }
 
     let shortcut_manager = app.global_shortcut();
     if let Ok(previous) = state.activation_shortcuts.lock().map(|guard| guard.clone()) {
@@ -1545,53 +2827,71 @@ fn register_overlay_hotkeys(
     state: &AppState,
     config: &AppConfig,
 ) -> Result<(), String> {
-    let keys = collect_overlay_keys(config);
-    let shortcut_manager = app.global_shortcut();
-
-    if let Ok(previous) = state.overlay_shortcuts.lock().map(|guard| guard.clone()) {
-        if !previous.is_empty() {
-            let _ = shortcut_manager.unregister_multiple(previous);
-        }
-    }
+    register_overlay_keys(app, state, collect_overlay_keys(config))
+}
 
-    if keys.is_empty() {
-        if let Ok(mut map) = state.overlay_key_map.lock() {
-            map.clear();
-        }
-        if let Ok(mut guard) = state.overlay_shortcuts.lock() {
-            guard.clear();
-        }
-        println!("[hotkeys] overlay keys empty");
-        return Ok(());
-    }
+// 只注销不再需要的键、注册新增的键：两组都有的键全程保持占用，不会漏到前台程序
+fn register_overlay_keys(
+    app: &AppHandle,
+    state: &AppState,
+    keys: Vec<String>,
+) -> Result<(), String> {
+    let shortcut_manager = app.global_shortcut();
 
     let mut key_map = HashMap::new();
     let mut shortcuts = Vec::new();
     for key in keys {
         if let Some(shortcut) = resolve_shortcut(&key) {
-            key_map.insert(shortcut.id(), key.clone());
-            shortcuts.push(shortcut);
+            if key_map.insert(shortcut.id(), key.clone()).is_none() {
+                shortcuts.push(shortcut);
+            }
         } else {
             println!("[hotkeys] invalid overlay key: {}", key);
         }
     }
 
-    if shortcuts.is_empty() {
-        println!("[hotkeys] overlay keys had no valid shortcuts");
+    let previous = state
+        .overlay_shortcuts
+        .lock()
+        .map(|guard| guard.clone())
+        .unwrap_or_default();
+    let stale: Vec<Shortcut> = previous
+        .iter()
+        .filter(|shortcut| !key_map.contains_key(&shortcut.id()))
+        .cloned()
+        .collect();
+    let added: Vec<Shortcut> = shortcuts
+        .iter()
+        .filter(|shortcut| !previous.iter().any(|old| old.id() == shortcut.id()))
+        .cloned()
+        .collect();
+    if stale.is_empty() && added.is_empty() {
         return Ok(());
     }
 
-    shortcut_manager
-        .register_multiple(shortcuts.clone())
-        .map_err(|e| e.to_string())?;
+    if !stale.is_empty() {
+        let _ = shortcut_manager.unregister_multiple(stale);
+    }
+    if let Ok(mut guard) = state.overlay_shortcuts.lock() {
+        guard.retain(|shortcut| key_map.contains_key(&shortcut.id()));
+    }
+    if !added.is_empty() {
+        shortcut_manager
+            .register_multiple(added)
+            .map_err(|e| e.to_string())?;
+    }
 
+    if shortcuts.is_empty() {
+        println!("[hotkeys] overlay keys empty");
+    } else {
+        println!("[hotkeys] overlay registered");
+    }
     if let Ok(mut guard) = state.overlay_shortcuts.lock() {
         *guard = shortcuts;
     }
     if let Ok(mut guard) = state.overlay_key_map.lock() {
         *guard = key_map;
     }
-    println!("[hotkeys] overlay registered");
 
     Ok(())
 }
@@ -1617,6 +2917,29 @@ fn is_next_monitor_key(value: &str, configured_key: &str) -> bool {
     value.eq_ignore_ascii_case(configured_key)
 }
 
+fn monitor_move_for_key(state: &AppState, key: &str) -> Option<MonitorMove> {
+    let monitor = state.config.lock().ok()?.monitor.clone();
+    let matches =
+        |configured: &String| !configured.trim().is_empty() && key.eq_ignore_ascii_case(configured);
+    if matches(&monitor.previous_key) {
+        return Some(MonitorMove::Previous);
+    }
+    if let Some(index) = monitor.jump_keys.iter().position(matches) {
+        return Some(MonitorMove::Jump(index));
+    }
+    let directions = monitor.direction_keys;
+    let candidates = [
+        (directions.left, Direction::Left),
+        (directions.right, Direction::Right),
+        (directions.up, Direction::Up),
+        (directions.down, Direction::Down),
+    ];
+    candidates
+        .into_iter()
+        .find(|(configured, _)| matches(configured))
+        .map(|(_, direction)| MonitorMove::Toward(direction))
+}
+
 fn is_switch_action_key(value: &str, configured_key: &str) -> bool {
     value.eq_ignore_ascii_case(configured_key)
 }
@@ -1644,8 +2967,8 @@ fn cycle_overlay_click_action(app: &AppHandle, state: &AppState) {
     };
 
     println!("[overlay] action switched to {:?}", next_action);
-    let _ = app.emit_to(
-        EventTarget::webview_window("overlay"),
+    emit_to_overlays(
+        app,
         "overlay:action",
         OverlayActionPayload {
             click_action: next_action,
@@ -1660,6 +2983,24 @@ fn is_nudge_key(value: &str) -> bool {
     )
 }
 
+fn is_bisect_key(state: &AppState, key: &str) -> bool {
+    let normalized = normalize_key(key);
+    let config = active_overlay_config(state);
+    let mut layers = config.layers.iter().chain(&config.finish.fallback_layer);
+    layers.any(|layer| match layer {
+        Layer::Bisect {
+            left,
+            right,
+            up,
+            down,
+            ..
+        } => [left, right, up, down]
+            .iter()
+            .any(|candidate| normalize_key(candidate) == normalized),
+        _ => false,
+    })
+}
+
 fn is_nudge_repeat_active(state: &AppState, key: &str) -> bool {
     if let Ok(guard) = state.nudge_repeat.lock() {
         if let Some(active) = guard.as_ref() {
@@ -1715,8 +3056,9 @@ fn start_nudge_repeat(app: AppHandle, state: &AppState, key: String) {
                 break;
             }
 
+            let target = key_target(app.state::<AppState>().inner());
             let _ = app.emit_to(
-                EventTarget::webview_window("overlay"),
+                EventTarget::webview_window(target),
                 "native:key",
                 NativeKeyPayload { key: key.clone() },
             );
@@ -0,0 +1,671 @@
+use crate::config::{MonitorMargins, StartStrategy};
+use serde::{Deserialize, Serialize};
+
+// 虚拟桌面上的物理像素矩形
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Region {
+    fn contains(&self, point: (f64, f64)) -> bool {
+        point.0 >= self.x
+            && point.0 < self.x + self.width
+            && point.1 >= self.y
+            && point.1 < self.y + self.height
+    }
+
+    fn intersection_area(&self, other: &Region) -> f64 {
+        let width = (self.x + self.width).min(other.x + other.width) - self.x.max(other.x);
+        let height = (self.y + self.height).min(other.y + other.height) - self.y.max(other.y);
+        width.max(0.0) * height.max(0.0)
+    }
+
+    pub fn intersection(&self, other: &Region) -> Option<Region> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let width = (self.x + self.width).min(other.x + other.width) - x;
+        let height = (self.y + self.height).min(other.y + other.height) - y;
+        (width > 0.0 && height > 0.0).then_some(Region {
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    fn span_x(&self) -> (f64, f64) {
+        (self.x, self.x + self.width)
+    }
+
+    fn span_y(&self) -> (f64, f64) {
+        (self.y, self.y + self.height)
+    }
+
+    fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    fn distance_squared(&self, point: (f64, f64)) -> f64 {
+        let dx = (self.x - point.0)
+            .max(point.0 - (self.x + self.width))
+            .max(0.0);
+        let dy = (self.y - point.1)
+            .max(point.1 - (self.y + self.height))
+            .max(0.0);
+        dx * dx + dy * dy
+    }
+}
+
+// 点落在显示器之间的空隙时取最近的显示器
+pub fn monitor_at(monitors: &[Region], point: (f64, f64)) -> Option<usize> {
+    if let Some(index) = monitors.iter().position(|monitor| monitor.contains(point)) {
+        return Some(index);
+    }
+    monitors
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| {
+            a.distance_squared(point)
+                .total_cmp(&b.distance_squared(point))
+        })
+        .map(|(index, _)| index)
+}
+
+#[derive(Debug, Clone)]
+pub struct MonitorInfo {
+    pub name: Option<String>,
+    pub region: Region,
+    pub primary: bool,
+}
+
+// 提供给设置页的显示器信息
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorSummary {
+    pub index: usize,
+    pub name: Option<String>,
+    // 位置与物理像素尺寸
+    pub full: Region,
+    pub work_area: Region,
+    // 按当前配置覆盖层实际覆盖的区域
+    pub usable: Region,
+    pub scale_factor: f64,
+    pub primary: bool,
+}
+
+// 热插拔只比较物理矩形，不看随配置变化的 usable
+pub fn hotplug_infos(summaries: &[MonitorSummary]) -> Vec<MonitorInfo> {
+    summaries
+        .iter()
+        .map(|summary| MonitorInfo {
+            name: summary.name.clone(),
+            region: summary.full.clone(),
+            primary: summary.primary,
+        })
+        .collect()
+}
+
+// 起始策略用到的信息；策略不需要的字段可以是 None
+#[derive(Debug, Clone, Default)]
+pub struct StartContext<'a> {
+    pub cursor: Option<(f64, f64)>,
+    pub focused_window: Option<Region>,
+    pub last_used: Option<&'a str>,
+}
+
+// 无法确定起始显示器时一律退回主显示器
+pub fn start_monitor_index(
+    monitors: &[MonitorInfo],
+    strategy: StartStrategy,
+    start_name: &str,
+    context: &StartContext,
+) -> usize {
+    let regions: Vec<Region> = monitors
+        .iter()
+        .map(|monitor| monitor.region.clone())
+        .collect();
+    let by_name = |name: &str| {
+        let name = name.trim();
+        monitors.iter().position(|monitor| {
+            !name.is_empty()
+                && monitor
+                    .name
+                    .as_deref()
+                    .is_some_and(|candidate| candidate.trim().eq_ignore_ascii_case(name))
+        })
+    };
+    let picked = match strategy {
+        StartStrategy::Primary => None,
+        StartStrategy::Cursor => context
+            .cursor
+            .and_then(|cursor| monitor_at(&regions, cursor)),
+        StartStrategy::FocusedWindow => context.focused_window.as_ref().and_then(|window| {
+            // 窗口跨屏时取覆盖面积最大的屏，完全落在屏外时取离中心最近的屏
+            let (index, area) = regions
+                .iter()
+                .map(|region| region.intersection_area(window))
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+            if area > 0.0 {
+                Some(index)
+            } else {
+                monitor_at(&regions, window.center())
+            }
+        }),
+        StartStrategy::LastUsed => context.last_used.and_then(by_name),
+        StartStrategy::Named => by_name(start_name),
+    };
+    picked
+        .or_else(|| monitors.iter().position(|monitor| monitor.primary))
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorMove {
+    Next,
+    Previous,
+    Jump(usize),
+    Toward(Direction),
+}
+
+pub fn target_monitor(monitors: &[Region], current: usize, step: MonitorMove) -> Option<usize> {
+    let count = monitors.len();
+    if count == 0 {
+        return None;
+    }
+    let current = current.min(count - 1);
+    match step {
+        MonitorMove::Next => Some((current + 1) % count),
+        MonitorMove::Previous => Some((current + count - 1) % count),
+        MonitorMove::Jump(index) => (index < count).then_some(index),
+        MonitorMove::Toward(direction) => neighbor_monitor(monitors, current, direction),
+    }
+}
+
+// 保存的序号可能来自显示器减少之前，越界时照样能移动
+pub fn advance_index(index: &mut usize, monitors: &[Region], step: MonitorMove) -> Option<usize> {
+    let next = target_monitor(monitors, *index, step)?;
+    *index = next;
+    Some(next)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotplugAction {
+    // 显示器仍在且没有变化，只是序号可能变了
+    Keep(usize),
+    // 显示器移动、改变大小或消失，在这个显示器上重新打开
+    Move(usize),
+    Cancel,
+}
+
+// 按名称匹配原来的显示器，没有名称时按矩形匹配；找不到时回到主显示器
+pub fn after_hotplug(before: &[MonitorInfo], after: &[MonitorInfo], index: usize) -> HotplugAction {
+    if after.is_empty() {
+        return HotplugAction::Cancel;
+    }
+    let matched = before.get(index).and_then(|old| {
+        let position = match &old.name {
+            Some(name) => after
+                .iter()
+                .position(|monitor| monitor.name.as_ref() == Some(name)),
+            None => after
+                .iter()
+                .position(|monitor| monitor.name.is_none() && monitor.region == old.region),
+        }?;
+        Some((position, after[position].region == old.region))
+    });
+    match matched {
+        Some((position, true)) => HotplugAction::Keep(position),
+        Some((position, false)) => HotplugAction::Move(position),
+        None => HotplugAction::Move(
+            after
+                .iter()
+                .position(|monitor| monitor.primary)
+                .unwrap_or(0),
+        ),
+    }
+}
+
+// 两个区间的间距：正数为空隙，负数为重叠长度
+fn separation(a: (f64, f64), b: (f64, f64)) -> f64 {
+    a.0.max(b.0) - a.1.min(b.1)
+}
+
+// 候选屏的中心须越过当前屏在该方向上的边；先选另一轴上重叠最多的，再选最近的
+fn neighbor_monitor(monitors: &[Region], current: usize, direction: Direction) -> Option<usize> {
+    let from = &monitors[current];
+    monitors
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != current)
+        .filter_map(|(index, candidate)| {
+            let (x, y) = candidate.center();
+            let ahead = match direction {
+                Direction::Left => from.x - x,
+                Direction::Right => x - (from.x + from.width),
+                Direction::Up => from.y - y,
+                Direction::Down => y - (from.y + from.height),
+            };
+            if ahead <= 0.0 {
+                return None;
+            }
+            let (across, along) = match direction {
+                Direction::Left | Direction::Right => (
+                    separation(from.span_y(), candidate.span_y()),
+                    separation(from.span_x(), candidate.span_x()),
+                ),
+                Direction::Up | Direction::Down => (
+                    separation(from.span_x(), candidate.span_x()),
+                    separation(from.span_y(), candidate.span_y()),
+                ),
+            };
+            Some((index, (across, along.max(0.0), ahead)))
+        })
+        .min_by(|(_, a), (_, b)| {
+            a.0.total_cmp(&b.0)
+                .then(a.1.total_cmp(&b.1))
+                .then(a.2.total_cmp(&b.2))
+        })
+        .map(|(index, _)| index)
+}
+
+// 优先取该显示器自己的边距，否则取不指定显示器的那一项
+pub fn margins_for<'a>(
+    margins: &'a [MonitorMargins],
+    name: Option<&str>,
+) -> Option<&'a MonitorMargins> {
+    name.and_then(|name| {
+        margins
+            .iter()
+            .find(|entry| entry.monitor.as_deref() == Some(name))
+    })
+    .or_else(|| margins.iter().find(|entry| entry.monitor.is_none()))
+}
+
+// 按需取工作区再减去边距；至少保留 1px，落在显示器外的工作区不予采用
+pub fn usable_region(
+    full: &Region,
+    work_area: Option<&Region>,
+    margins: Option<&MonitorMargins>,
+) -> Region {
+    let base = work_area
+        .and_then(|area| full.intersection(area))
+        .unwrap_or_else(|| full.clone());
+    let Some(margins) = margins else {
+        return base;
+    };
+    let horizontal = (margins.left + margins.right) as f64;
+    let vertical = (margins.top + margins.bottom) as f64;
+    let width = (base.width - horizontal).max(1.0);
+    let height = (base.height - vertical).max(1.0);
+    Region {
+        x: (base.x + margins.left as f64).min(base.x + base.width - width),
+        y: (base.y + margins.top as f64).min(base.y + base.height - height),
+        width,
+        height,
+    }
+}
+
+// 所有显示器的外接矩形；空隙和矮屏下方也算在内，但不在任何屏幕上
+pub fn bounding_region(monitors: &[Region]) -> Option<Region> {
+    let first = monitors.first()?;
+    let (mut left, mut right) = first.span_x();
+    let (mut top, mut bottom) = first.span_y();
+    for monitor in &monitors[1..] {
+        let (start_x, end_x) = monitor.span_x();
+        let (start_y, end_y) = monitor.span_y();
+        left = left.min(start_x);
+        right = right.max(end_x);
+        top = top.min(start_y);
+        bottom = bottom.max(end_y);
+    }
+    Some(Region {
+        x: left,
+        y: top,
+        width: (right - left).max(1.0),
+        height: (bottom - top).max(1.0),
+    })
+}
+
+// region 中没有显示器覆盖的比例，假定显示器互不重叠
+pub fn uncovered_ratio(monitors: &[Region], region: &Region) -> f64 {
+    let area = region.width * region.height;
+    if area <= 0.0 {
+        return 0.0;
+    }
+    let covered: f64 = monitors
+        .iter()
+        .map(|monitor| monitor.intersection_area(region))
+        .sum();
+    (1.0 - covered / area).max(0.0)
+}
+
+// 以 point 为中心，超出 bounds 时平移回来
+pub fn centered_region(point: (f64, f64), width: f64, height: f64, bounds: &Region) -> Region {
+    let width = width.clamp(1.0, bounds.width.max(1.0));
+    let height = height.clamp(1.0, bounds.height.max(1.0));
+    let max_x = bounds.x + bounds.width - width;
+    let max_y = bounds.y + bounds.height - height;
+    Region {
+        x: (point.0 - width / 2.0)
+            .round()
+            .clamp(bounds.x, max_x.max(bounds.x)),
+        y: (point.1 - height / 2.0)
+            .round()
+            .clamp(bounds.y, max_y.max(bounds.y)),
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(x: f64, y: f64, width: f64, height: f64) -> Region {
+        Region {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    fn side_by_side() -> Vec<Region> {
+        vec![
+            region(0.0, 0.0, 1920.0, 1080.0),
+            region(1920.0, -200.0, 2560.0, 1440.0),
+        ]
+    }
+
+    #[test]
+    fn finds_monitor_under_point() {
+        let monitors = side_by_side();
+        assert_eq!(monitor_at(&monitors, (100.0, 100.0)), Some(0));
+        assert_eq!(monitor_at(&monitors, (1920.0, 0.0)), Some(1));
+        // 落在两屏之间的空隙时取最近的屏
+        assert_eq!(monitor_at(&monitors, (500.0, 1150.0)), Some(0));
+        assert_eq!(monitor_at(&[], (0.0, 0.0)), None);
+    }
+
+    #[test]
+    fn bounding_region_spans_gaps_and_unequal_heights() {
+        assert_eq!(bounding_region(&[]), None);
+
+        let monitors = side_by_side();
+        let bounds = bounding_region(&monitors).unwrap();
+        assert_eq!(bounds, region(0.0, -200.0, 4480.0, 1440.0));
+        // 左屏上方 200px 与下方 160px 不在任何屏幕上
+        let expected = (1920.0 * 360.0) / (4480.0 * 1440.0);
+        assert!((uncovered_ratio(&monitors, &bounds) - expected).abs() < 1e-9);
+
+        let gap = [
+            region(0.0, 0.0, 1920.0, 1080.0),
+            region(2020.0, 0.0, 1920.0, 1080.0),
+        ];
+        let bounds = bounding_region(&gap).unwrap();
+        assert_eq!(bounds, region(0.0, 0.0, 3940.0, 1080.0));
+        assert_eq!(
+            uncovered_ratio(&gap, &region(1920.0, 0.0, 100.0, 1080.0)),
+            1.0
+        );
+    }
+
+    fn margins(monitor: Option<&str>, top: u32, bottom: u32) -> MonitorMargins {
+        MonitorMargins {
+            monitor: monitor.map(str::to_string),
+            top,
+            bottom,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn usable_region_uses_work_area_and_margins() {
+        let full = region(1920.0, 0.0, 2560.0, 1440.0);
+        // 底部 48px 任务栏
+        let work = region(1920.0, 0.0, 2560.0, 1392.0);
+        assert_eq!(usable_region(&full, None, None), full);
+        assert_eq!(usable_region(&full, Some(&work), None), work);
+
+        let top_bar = margins(None, 32, 0);
+        assert_eq!(
+            usable_region(&full, Some(&work), Some(&top_bar)),
+            region(1920.0, 32.0, 2560.0, 1360.0)
+        );
+
+        // 错误的工作区与过大的边距都不会让区域消失
+        let elsewhere = region(0.0, 0.0, 100.0, 100.0);
+        assert_eq!(usable_region(&full, Some(&elsewhere), None), full);
+        let huge = margins(None, 1000, 1000);
+        let squeezed = usable_region(&full, None, Some(&huge));
+        assert_eq!((squeezed.height, squeezed.y), (1.0, 1000.0));
+    }
+
+    #[test]
+    fn named_margins_override_the_default() {
+        let entries = vec![margins(None, 10, 0), margins(Some("HDMI-1"), 0, 40)];
+        assert_eq!(margins_for(&entries, Some("HDMI-1")), Some(&entries[1]));
+        assert_eq!(margins_for(&entries, Some("DP-1")), Some(&entries[0]));
+        assert_eq!(margins_for(&entries, None), Some(&entries[0]));
+        assert_eq!(margins_for(&entries[1..], Some("DP-1")), None);
+    }
+
+    fn three_monitors() -> Vec<MonitorInfo> {
+        [
+            ("LEFT", region(-1920.0, 0.0, 1920.0, 1080.0), false),
+            ("MAIN", region(0.0, 0.0, 2560.0, 1440.0), true),
+            ("RIGHT", region(2560.0, 0.0, 1920.0, 1080.0), false),
+        ]
+        .into_iter()
+        .map(|(name, region, primary)| MonitorInfo {
+            name: Some(name.to_string()),
+            region,
+            primary,
+        })
+        .collect()
+    }
+
+    #[test]
+    fn start_strategies_pick_expected_monitor() {
+        let monitors = three_monitors();
+        let context = StartContext {
+            cursor: Some((3000.0, 500.0)),
+            // 大部分在左屏，少部分压到主屏
+            focused_window: Some(region(-1500.0, 100.0, 1800.0, 800.0)),
+            last_used: Some("right"),
+        };
+        let pick = |strategy, name| start_monitor_index(&monitors, strategy, name, &context);
+
+        assert_eq!(pick(StartStrategy::Primary, ""), 1);
+        assert_eq!(pick(StartStrategy::Cursor, ""), 2);
+        assert_eq!(pick(StartStrategy::FocusedWindow, ""), 0);
+        assert_eq!(pick(StartStrategy::LastUsed, ""), 2);
+        assert_eq!(pick(StartStrategy::Named, " Left "), 0);
+    }
+
+    #[test]
+    fn start_strategies_fall_back_to_primary() {
+        let monitors = three_monitors();
+        let empty = StartContext::default();
+        for strategy in [
+            StartStrategy::Cursor,
+            StartStrategy::FocusedWindow,
+            StartStrategy::LastUsed,
+            StartStrategy::Named,
+        ] {
+            assert_eq!(start_monitor_index(&monitors, strategy, "", &empty), 1);
+        }
+        assert_eq!(
+            start_monitor_index(&monitors, StartStrategy::Named, "HDMI-9", &empty),
+            1
+        );
+
+        // 窗口在所有屏之外时取离窗口中心最近的屏
+        let offscreen = StartContext {
+            focused_window: Some(region(5000.0, 200.0, 400.0, 300.0)),
+            ..StartContext::default()
+        };
+        assert_eq!(
+            start_monitor_index(&monitors, StartStrategy::FocusedWindow, "", &offscreen),
+            2
+        );
+
+        // 没有主屏信息时取第一个
+        let mut unmarked = three_monitors();
+        unmarked
+            .iter_mut()
+            .for_each(|monitor| monitor.primary = false);
+        assert_eq!(
+            start_monitor_index(&unmarked, StartStrategy::Primary, "", &empty),
+            0
+        );
+    }
+
+    #[test]
+    fn cycles_and_jumps_between_monitors() {
+        let monitors: Vec<Region> = three_monitors().into_iter().map(|m| m.region).collect();
+        assert_eq!(target_monitor(&monitors, 2, MonitorMove::Next), Some(0));
+        assert_eq!(target_monitor(&monitors, 0, MonitorMove::Previous), Some(2));
+        assert_eq!(target_monitor(&monitors, 0, MonitorMove::Jump(1)), Some(1));
+        assert_eq!(target_monitor(&monitors, 0, MonitorMove::Jump(3)), None);
+        assert_eq!(target_monitor(&[], 0, MonitorMove::Next), None);
+    }
+
+    #[test]
+    fn stored_index_survives_a_shrinking_list() {
+        let mut monitors: Vec<Region> = three_monitors().into_iter().map(|m| m.region).collect();
+        let mut index = 0;
+        assert_eq!(
+            advance_index(&mut index, &monitors, MonitorMove::Next),
+            Some(1)
+        );
+        assert_eq!(
+            advance_index(&mut index, &monitors, MonitorMove::Next),
+            Some(2)
+        );
+
+        // 右屏拔掉后，保存的序号 2 已越界
+        monitors.pop();
+        assert_eq!(
+            advance_index(&mut index, &monitors, MonitorMove::Next),
+            Some(0)
+        );
+        assert_eq!(index, 0);
+
+        index = 2;
+        assert_eq!(
+            advance_index(&mut index, &monitors, MonitorMove::Previous),
+            Some(0)
+        );
+        index = 2;
+        assert_eq!(
+            advance_index(&mut index, &monitors, MonitorMove::Jump(2)),
+            None
+        );
+        assert_eq!(index, 2);
+
+        monitors.clear();
+        assert_eq!(
+            advance_index(&mut index, &monitors, MonitorMove::Next),
+            None
+        );
+    }
+
+    fn summaries(monitors: &[MonitorInfo]) -> Vec<MonitorSummary> {
+        monitors
+            .iter()
+            .enumerate()
+            .map(|(index, monitor)| MonitorSummary {
+                index,
+                name: monitor.name.clone(),
+                full: monitor.region.clone(),
+                work_area: monitor.region.clone(),
+                usable: monitor.region.clone(),
+                scale_factor: 1.0,
+                primary: monitor.primary,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn hotplug_keeps_moves_or_cancels() {
+        let before = three_monitors();
+
+        // 左屏拔掉：主屏序号前移但位置不变
+        let after: Vec<MonitorInfo> = before[1..].to_vec();
+        assert_eq!(after_hotplug(&before, &after, 1), HotplugAction::Keep(0));
+        // 覆盖层所在的左屏没了，回到主显示器
+        assert_eq!(after_hotplug(&before, &after, 0), HotplugAction::Move(0));
+        // 保存的序号已越界时同样回到主显示器
+        assert_eq!(after_hotplug(&before, &after, 5), HotplugAction::Move(0));
+
+        let mut resized = before.clone();
+        resized[2].region.width = 1280.0;
+        assert_eq!(after_hotplug(&before, &resized, 2), HotplugAction::Move(2));
+
+        assert_eq!(after_hotplug(&before, &[], 1), HotplugAction::Cancel);
+    }
+
+    #[test]
+    fn usable_only_change_keeps_overlay() {
+        let before = summaries(&three_monitors());
+        let mut after = before.clone();
+        for summary in &mut after {
+            summary.usable.height -= 48.0;
+        }
+        assert_eq!(
+            after_hotplug(&hotplug_infos(&before), &hotplug_infos(&after), 1),
+            HotplugAction::Keep(1)
+        );
+    }
+
+    #[test]
+    fn moves_to_spatial_neighbors() {
+        // 左屏 | 主屏 | 右屏，主屏上方还有一块错开的屏
+        let mut monitors: Vec<Region> = three_monitors().into_iter().map(|m| m.region).collect();
+        monitors.push(region(800.0, -1080.0, 1920.0, 1080.0));
+        let toward =
+            |from, direction| target_monitor(&monitors, from, MonitorMove::Toward(direction));
+
+        assert_eq!(toward(1, Direction::Left), Some(0));
+        assert_eq!(toward(1, Direction::Right), Some(2));
+        assert_eq!(toward(1, Direction::Up), Some(3));
+        assert_eq!(toward(0, Direction::Left), None);
+        assert_eq!(toward(3, Direction::Down), Some(1));
+        // 上方屏的右侧没有对齐的屏时，取中心在右侧且最近的
+        assert_eq!(toward(3, Direction::Right), Some(2));
+        assert_eq!(toward(2, Direction::Up), Some(3));
+    }
+
+    #[test]
+    fn centers_box_and_clamps_to_bounds() {
+        let bounds = region(0.0, 0.0, 1920.0, 1080.0);
+        assert_eq!(
+            centered_region((960.0, 540.0), 300.0, 200.0, &bounds),
+            region(810.0, 440.0, 300.0, 200.0)
+        );
+        assert_eq!(
+            centered_region((10.0, 1075.0), 300.0, 200.0, &bounds),
+            region(0.0, 880.0, 300.0, 200.0)
+        );
+        // 比屏幕还大时缩到整个屏幕
+        assert_eq!(
+            centered_region((10.0, 10.0), 4000.0, 200.0, &bounds),
+            region(0.0, 0.0, 1920.0, 200.0)
+        );
+    }
+}
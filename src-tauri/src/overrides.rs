@@ -0,0 +1,237 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+// 导入方式：replace 以默认配置为基准（旧行为），merge / patch 以当前配置为基准
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportMode {
+    #[default]
+    Replace,
+    Merge,
+    Patch,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigChange {
+    // 变化处的 JSON Pointer，例如 /layers/1/keys/3
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Value>,
+}
+
+pub fn diff_value(default: &Value, current: &Value) -> Option<Value> {
+    if default == current {
+        return None;
+    }
+
+    match (default, current) {
+        (Value::Object(default_map), Value::Object(current_map)) => {
+            let mut out = Map::new();
+            for (key, current_value) in current_map {
+                if let Some(default_value) = default_map.get(key) {
+                    if let Some(next) = diff_value(default_value, current_value) {
+                        out.insert(key.clone(), next);
+                    }
+                } else {
+                    out.insert(key.clone(), current_value.clone());
+                }
+            }
+            if out.is_empty() {
+                None
+            } else {
+                Some(Value::Object(out))
+            }
+        }
+        _ => Some(current.clone()),
+    }
+}
+
+pub fn merge_value(default: &Value, overrides: &Value) -> Value {
+    match (default, overrides) {
+        (Value::Object(default_map), Value::Object(override_map)) => {
+            let mut out = default_map.clone();
+            for (key, override_value) in override_map {
+                let merged = if let Some(default_value) = out.get(key) {
+                    merge_value(default_value, override_value)
+                } else {
+                    override_value.clone()
+                };
+                out.insert(key.clone(), merged);
+            }
+            Value::Object(out)
+        }
+        (_, override_leaf) => override_leaf.clone(),
+    }
+}
+
+// 返回的配置尚未校验。merge 按 RFC 7396、patch 按 RFC 6902 作用于当前配置，
+// 被删掉的字段（如 merge 里的 null）回到默认值
+pub fn apply_import(
+    defaults: &Value,
+    current: &Value,
+    document: &Value,
+    mode: ImportMode,
+) -> Result<Value, String> {
+    match mode {
+        ImportMode::Replace => {
+            if !document.is_object() {
+                return Err("override document must be an object".to_string());
+            }
+            Ok(merge_value(defaults, document))
+        }
+        ImportMode::Merge => {
+            if !document.is_object() {
+                return Err("merge patch must be an object".to_string());
+            }
+            let mut next = current.clone();
+            json_patch::merge(&mut next, document);
+            Ok(merge_value(defaults, &next))
+        }
+        ImportMode::Patch => {
+            let operations: json_patch::Patch = serde_json::from_value(document.clone())
+                .map_err(|e| format!("invalid JSON Patch: {}", e))?;
+            let mut next = current.clone();
+            json_patch::patch(&mut next, &operations).map_err(|e| e.to_string())?;
+            Ok(merge_value(defaults, &next))
+        }
+    }
+}
+
+// 数组逐项比较，layers 里只改一个键时只列出一条
+pub fn config_changes(before: &Value, after: &Value) -> Vec<ConfigChange> {
+    let mut changes = Vec::new();
+    collect_changes(String::new(), Some(before), Some(after), &mut changes);
+    changes
+}
+
+fn collect_changes(
+    path: String,
+    before: Option<&Value>,
+    after: Option<&Value>,
+    out: &mut Vec<ConfigChange>,
+) {
+    match (before, after) {
+        (Some(Value::Object(before_map)), Some(Value::Object(after_map))) => {
+            let mut keys: Vec<&String> = before_map.keys().chain(after_map.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                collect_changes(
+                    format!("{}/{}", path, escape_pointer_token(key)),
+                    before_map.get(key),
+                    after_map.get(key),
+                    out,
+                );
+            }
+        }
+        (Some(Value::Array(before_items)), Some(Value::Array(after_items))) => {
+            for index in 0..before_items.len().max(after_items.len()) {
+                collect_changes(
+                    format!("{}/{}", path, index),
+                    before_items.get(index),
+                    after_items.get(index),
+                    out,
+                );
+            }
+        }
+        (before, after) => {
+            if before != after {
+                out.push(ConfigChange {
+                    path,
+                    before: before.cloned(),
+                    after: after.cloned(),
+                });
+            }
+        }
+    }
+}
+
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn defaults() -> Value {
+        json!({
+            "nudge": { "stepPx": 5 },
+            "overlay": { "alpha": 120, "lineWidthPx": 1 },
+            "layers": [
+                { "mode": "single", "rows": 1, "cols": 3, "keys": ["a", "s", "d"] },
+                { "mode": "single", "rows": 1, "cols": 2, "keys": ["j", "k"] }
+            ]
+        })
+    }
+
+    fn current() -> Value {
+        let mut value = defaults();
+        value["nudge"]["stepPx"] = json!(9);
+        value["overlay"]["alpha"] = json!(200);
+        value
+    }
+
+    #[test]
+    fn replace_resolves_against_defaults() {
+        let doc = json!({ "overlay": { "lineWidthPx": 2 } });
+        let next = apply_import(&defaults(), &current(), &doc, ImportMode::Replace).unwrap();
+        assert_eq!(next["nudge"]["stepPx"], json!(5));
+        assert_eq!(next["overlay"]["alpha"], json!(120));
+        assert_eq!(next["overlay"]["lineWidthPx"], json!(2));
+    }
+
+    #[test]
+    fn merge_keeps_current_customizations_and_null_resets() {
+        let doc = json!({ "overlay": { "lineWidthPx": 2, "alpha": null } });
+        let next = apply_import(&defaults(), &current(), &doc, ImportMode::Merge).unwrap();
+        assert_eq!(next["nudge"]["stepPx"], json!(9));
+        assert_eq!(next["overlay"]["lineWidthPx"], json!(2));
+        assert_eq!(next["overlay"]["alpha"], json!(120));
+    }
+
+    #[test]
+    fn patch_can_target_a_single_key_inside_layers() {
+        let doc = json!([
+            { "op": "test", "path": "/layers/1/keys/1", "value": "k" },
+            { "op": "replace", "path": "/layers/1/keys/1", "value": "l" }
+        ]);
+        let next = apply_import(&defaults(), &current(), &doc, ImportMode::Patch).unwrap();
+        assert_eq!(next["layers"][1]["keys"], json!(["j", "l"]));
+        assert_eq!(next["layers"][0], defaults()["layers"][0]);
+        assert_eq!(next["nudge"]["stepPx"], json!(9));
+    }
+
+    #[test]
+    fn failed_patch_reports_error() {
+        let doc = json!([{ "op": "test", "path": "/nudge/stepPx", "value": 1 }]);
+        assert!(apply_import(&defaults(), &current(), &doc, ImportMode::Patch).is_err());
+        let not_a_patch = json!({ "nudge": { "stepPx": 1 } });
+        assert!(apply_import(&defaults(), &current(), &not_a_patch, ImportMode::Patch).is_err());
+    }
+
+    #[test]
+    fn changes_are_reported_per_leaf() {
+        let mut after = current();
+        after["layers"][1]["keys"][1] = json!("l");
+        after["overlay"]["alpha"] = json!(120);
+        after["layers"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({ "mode": "single", "rows": 1, "cols": 1, "keys": ["x"] }));
+
+        let changes = config_changes(&current(), &after);
+        let paths: Vec<&str> = changes.iter().map(|change| change.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["/layers/1/keys/1", "/layers/2", "/overlay/alpha"]
+        );
+        assert_eq!(changes[0].before, Some(json!("k")));
+        assert_eq!(changes[0].after, Some(json!("l")));
+        assert_eq!(changes[1].before, None);
+    }
+}
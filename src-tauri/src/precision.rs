@@ -0,0 +1,370 @@
+use crate::config::{AppConfig, AutoFinishAction, Layer};
+use serde::Serialize;
+
+// 最终格子超过该物理像素时提示精度不足
+pub const DEFAULT_MAX_CELL_PX: f64 = 64.0;
+
+#[derive(Debug, Clone)]
+pub struct MonitorSize {
+    pub name: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PrecisionFlag {
+    // 该层把格子切得比一个物理像素还小
+    SubPixel,
+    // 最后一层的格子仍大于阈值
+    Coarse,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayerPrecision {
+    pub layer: usize,
+    pub keystrokes: u32,
+    pub cell_width: f64,
+    pub cell_height: f64,
+    pub flags: Vec<PrecisionFlag>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorPrecision {
+    pub index: usize,
+    pub name: Option<String>,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    pub layers: Vec<LayerPrecision>,
+    pub final_cell_width: f64,
+    pub final_cell_height: f64,
+    pub final_logical_width: f64,
+    pub final_logical_height: f64,
+    pub keystrokes: u32,
+}
+
+// 不低于 1px 的最多对半次数
+fn halvings_to_pixel(length: f64) -> u32 {
+    if length < 2.0 {
+        0
+    } else {
+        length.log2().floor() as u32
+    }
+}
+
+// 按权重划分时取最宽的一条
+fn largest_band_share(count: u32, weights: Option<&[f64]>) -> f64 {
+    match weights {
+        Some(weights) if weights.len() == count as usize && count > 0 => {
+            let total: f64 = weights.iter().sum();
+            weights.iter().cloned().fold(0.0, f64::max) / total
+        }
+        _ => 1.0 / count.max(1) as f64,
+    }
+}
+
+// 重叠扩展后的占比，不超过整块区域
+fn overlapped_share(share: f64, overlap_ratio: Option<f64>) -> f64 {
+    (share * (1.0 + 2.0 * overlap_ratio.unwrap_or(0.0))).min(1.0)
+}
+
+// 返回最大格子占原区域的比例（高、宽）与按键次数；
+// 二分层按“切到像素级再确认”估算最坏情况
+fn layer_shares(layer: &Layer, width: f64, height: f64) -> (f64, f64, u32) {
+    match layer {
+        Layer::Single {
+            rows,
+            cols,
+            row_weights,
+            col_weights,
+            overlap_ratio,
+            ..
+        } => (
+            overlapped_share(
+                largest_band_share(*rows, row_weights.as_deref()),
+                *overlap_ratio,
+            ),
+            overlapped_share(
+                largest_band_share(*cols, col_weights.as_deref()),
+                *overlap_ratio,
+            ),
+            1,
+        ),
+        Layer::Combo {
+            stages,
+            overlap_ratio,
+        } => stages
+            .iter()
+            .fold((1.0, 1.0, 0), |(rows, cols, keystrokes), stage| {
+                let row_share = largest_band_share(stage.rows, stage.row_weights.as_deref());
+                let col_share = largest_band_share(stage.cols, stage.col_weights.as_deref());
+                (
+                    rows * overlapped_share(row_share, *overlap_ratio),
+                    cols * overlapped_share(col_share, *overlap_ratio),
+                    keystrokes + 1,
+                )
+            }),
+        Layer::Bisect { .. } => {
+            let horizontal = halvings_to_pixel(width);
+            let vertical = halvings_to_pixel(height);
+            (
+                1.0 / (1u64 << vertical) as f64,
+                1.0 / (1u64 << horizontal) as f64,
+                horizontal + vertical + 1,
+            )
+        }
+        // 定点层一次按键直接落到像素点
+        Layer::Points { .. } => (1.0 / height.ceil(), 1.0 / width.ceil(), 1),
+    }
+}
+
+// 尺寸按物理像素计，最后一格另外给出逻辑像素
+pub fn monitor_precision(
+    index: usize,
+    monitor: &MonitorSize,
+    layers: &[Layer],
+    max_cell_px: f64,
+) -> MonitorPrecision {
+    let mut cell_width = monitor.width.max(1) as f64;
+    let mut cell_height = monitor.height.max(1) as f64;
+    let mut keystrokes = 0;
+    let mut reports = Vec::with_capacity(layers.len());
+
+    for (layer_index, layer) in layers.iter().enumerate() {
+        let (row_share, col_share, layer_keystrokes) = layer_shares(layer, cell_width, cell_height);
+        cell_width *= col_share;
+        cell_height *= row_share;
+        keystrokes += layer_keystrokes;
+
+        let mut flags = Vec::new();
+        if cell_width < 1.0 || cell_height < 1.0 {
+            flags.push(PrecisionFlag::SubPixel);
+        }
+        if layer_index + 1 == layers.len()
+            && (cell_width > max_cell_px || cell_height > max_cell_px)
+        {
+            flags.push(PrecisionFlag::Coarse);
+        }
+        reports.push(LayerPrecision {
+            layer: layer_index,
+            keystrokes: layer_keystrokes,
+            cell_width,
+            cell_height,
+            flags,
+        });
+    }
+
+    let scale = if monitor.scale_factor.is_finite() && monitor.scale_factor > 0.0 {
+        monitor.scale_factor
+    } else {
+        1.0
+    };
+    MonitorPrecision {
+        index,
+        name: monitor.name.clone(),
+        width: monitor.width,
+        height: monitor.height,
+        scale_factor: scale,
+        layers: reports,
+        final_cell_width: cell_width,
+        final_cell_height: cell_height,
+        final_logical_width: cell_width / scale,
+        final_logical_height: cell_height / scale,
+        keystrokes,
+    }
+}
+
+// 按 config.finish 计算：到达 autoBelowPx 的层即停止，最后一层仍大于
+// fallbackAbovePx 时追加兜底层
+pub fn config_precision(
+    index: usize,
+    monitor: &MonitorSize,
+    config: &AppConfig,
+    max_cell_px: f64,
+) -> MonitorPrecision {
+    let finish = &config.finish;
+    let mut layers = config.layers.clone();
+    if let Some(fallback) = &finish.fallback_layer {
+        let threshold = finish.fallback_above_px as f64;
+        let report = monitor_precision(index, monitor, &layers, max_cell_px);
+        if finish.fallback_above_px > 0
+            && (report.final_cell_width > threshold || report.final_cell_height > threshold)
+        {
+            layers.push(fallback.clone());
+        }
+    }
+
+    let mut nudge_only = false;
+    if finish.auto_below_px > 0 {
+        let threshold = finish.auto_below_px as f64;
+        let report = monitor_precision(index, monitor, &layers, max_cell_px);
+        if let Some(stop) = report
+            .layers
+            .iter()
+            .position(|layer| layer.cell_width < threshold && layer.cell_height < threshold)
+        {
+            layers.truncate(stop + 1);
+            nudge_only = finish.auto_action == AutoFinishAction::Nudge;
+        }
+    }
+
+    let mut report = monitor_precision(index, monitor, &layers, max_cell_px);
+    // 锚点步骤在最后一层之后多按一次键；定点层与微调收尾不经过锚点
+    let ends_in_points = matches!(layers.last(), Some(Layer::Points { .. }));
+    if !finish.anchor_keys.is_empty() && !ends_in_points && !nudge_only {
+        report.keystrokes += 1;
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::default_config;
+
+    fn monitor_4k() -> MonitorSize {
+        MonitorSize {
+            name: Some("DP-1".to_string()),
+            width: 3840,
+            height: 2160,
+            scale_factor: 1.5,
+        }
+    }
+
+    #[test]
+    fn reports_default_layers_on_4k() {
+        let config = default_config();
+        let report = monitor_precision(0, &monitor_4k(), &config.layers, DEFAULT_MAX_CELL_PX);
+
+        // 默认：15x15 组合层 + 3x5 单层
+        assert_eq!(report.keystrokes, 3);
+        assert_eq!(report.layers.len(), 2);
+        assert_eq!(report.layers[0].keystrokes, 2);
+        assert!((report.layers[0].cell_width - 256.0).abs() < 1e-9);
+        assert!((report.layers[0].cell_height - 144.0).abs() < 1e-9);
+        assert!((report.final_cell_width - 51.2).abs() < 1e-9);
+        assert!((report.final_cell_height - 48.0).abs() < 1e-9);
+        assert!((report.final_logical_width - 51.2 / 1.5).abs() < 1e-9);
+        assert!(report.layers.iter().all(|layer| layer.flags.is_empty()));
+
+        let strict = monitor_precision(0, &monitor_4k(), &config.layers, 16.0);
+        assert!(strict.layers[0].flags.is_empty());
+        assert_eq!(strict.layers[1].flags, vec![PrecisionFlag::Coarse]);
+    }
+
+    #[test]
+    fn bisect_counts_halvings_to_one_pixel() {
+        let layers = vec![Layer::Bisect {
+            left: "h".to_string(),
+            right: "l".to_string(),
+            up: "k".to_string(),
+            down: "j".to_string(),
+            finish: ";".to_string(),
+        }];
+
+        let report = monitor_precision(0, &monitor_4k(), &layers, DEFAULT_MAX_CELL_PX);
+        // 3840 与 2160 各对半 11 次，再加一次确认
+        assert_eq!(report.keystrokes, 23);
+        assert!((report.final_cell_width - 1.875).abs() < 1e-9);
+        assert!((1.0..2.0).contains(&report.final_cell_height));
+        assert!(report.layers[0].flags.is_empty());
+    }
+
+    #[test]
+    fn flags_sub_pixel_layers() {
+        let layers = vec![
+            Layer::Single {
+                rows: 1,
+                cols: 2,
+                keys: vec!["a".to_string(), "b".to_string()],
+                row_weights: None,
+                col_weights: None,
+                overlap_ratio: None,
+            };
+            3
+        ];
+        let monitor = MonitorSize {
+            name: None,
+            width: 6,
+            height: 6,
+            scale_factor: 1.0,
+        };
+
+        let report = monitor_precision(0, &monitor, &layers, DEFAULT_MAX_CELL_PX);
+        assert!(report.layers[1].flags.is_empty());
+        assert_eq!(report.layers[2].flags, vec![PrecisionFlag::SubPixel]);
+        assert!((report.final_cell_width - 0.75).abs() < 1e-9);
+        assert_eq!(report.keystrokes, 3);
+    }
+
+    #[test]
+    fn weighted_bands_report_the_largest_cell() {
+        let layers = vec![Layer::Single {
+            rows: 3,
+            cols: 2,
+            keys: ["a", "b", "c", "d", "e", "f"].map(String::from).to_vec(),
+            row_weights: Some(vec![1.0, 1.0, 3.0]),
+            col_weights: Some(vec![1.0, 1.0]),
+            overlap_ratio: None,
+        }];
+        let report = monitor_precision(0, &monitor_4k(), &layers, DEFAULT_MAX_CELL_PX);
+        assert!((report.final_cell_width - 1920.0).abs() < 1e-9);
+        assert!((report.final_cell_height - 1296.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn overlap_grows_cells_up_to_the_monitor() {
+        let layer = |cols: u32, overlap_ratio: f64| Layer::Single {
+            rows: 1,
+            cols,
+            keys: (0..cols).map(|index| index.to_string()).collect(),
+            row_weights: None,
+            col_weights: None,
+            overlap_ratio: Some(overlap_ratio),
+        };
+
+        let report = monitor_precision(0, &monitor_4k(), &[layer(4, 0.1)], 1e9);
+        assert!((report.final_cell_width - 960.0 * 1.2).abs() < 1e-9);
+        // 单行的高度已是整个显示器，扩展后仍不超出
+        assert!((report.final_cell_height - 2160.0).abs() < 1e-9);
+
+        let report = monitor_precision(0, &monitor_4k(), &[layer(2, 0.5)], 1e9);
+        assert!((report.final_cell_width - 3840.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn follows_auto_finish_and_fallback() {
+        let mut config = default_config();
+        config.finish.auto_below_px = 300;
+        let report = config_precision(0, &monitor_4k(), &config, DEFAULT_MAX_CELL_PX);
+        // 组合层后已是 256x144，直接结束
+        assert_eq!(report.layers.len(), 1);
+        assert_eq!(report.keystrokes, 2);
+
+        // 锚点多一次按键，微调收尾则跳过锚点
+        config.finish.anchor_keys = ["q", "w", "e", "a", "s", "d", "z", "x", "c"]
+            .map(String::from)
+            .to_vec();
+        let report = config_precision(0, &monitor_4k(), &config, DEFAULT_MAX_CELL_PX);
+        assert_eq!(report.keystrokes, 3);
+        config.finish.auto_action = AutoFinishAction::Nudge;
+        let report = config_precision(0, &monitor_4k(), &config, DEFAULT_MAX_CELL_PX);
+        assert_eq!(report.keystrokes, 2);
+        config.finish.anchor_keys.clear();
+
+        config.finish.auto_below_px = 0;
+        config.finish.fallback_above_px = 40;
+        config.finish.fallback_layer = Some(config.layers[1].clone());
+        let report = config_precision(0, &monitor_4k(), &config, DEFAULT_MAX_CELL_PX);
+        assert_eq!(report.layers.len(), 3);
+        assert!((report.final_cell_height - 16.0).abs() < 1e-9);
+
+        config.finish.fallback_above_px = 60;
+        let report = config_precision(0, &monitor_4k(), &config, DEFAULT_MAX_CELL_PX);
+        assert_eq!(report.layers.len(), 2);
+    }
+}
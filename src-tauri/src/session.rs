@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CloseReason {
+    Click,
+    // 取消键，或在第一层继续撤销
+    Cancel,
+    Error,
+    // overlay.idleTimeoutMs 内没有按覆盖层按键
+    IdleTimeout,
+    FocusChanged,
+    // 覆盖层所在的显示器被拔掉或发生变化
+    MonitorsChanged,
+    // 覆盖层打开期间配置被保存或重新加载
+    ConfigChanged,
+    // 从托盘暂停
+    Paused,
+}
+
+// 一次打开的覆盖层：最后一次按键时间和打开时的前台窗口
+#[derive(Debug, Clone)]
+pub struct OverlaySession {
+    pub id: u64,
+    focus: Option<u64>,
+    last_activity: Instant,
+}
+
+impl OverlaySession {
+    pub fn new(id: u64, focus: Option<u64>, now: Instant) -> Self {
+        Self {
+            id,
+            focus,
+            last_activity: now,
+        }
+    }
+
+    pub fn touch(&mut self, now: Instant) {
+        self.last_activity = now;
+    }
+
+    // 超时为 0 时永不过期；两边的前台窗口都已知才算焦点变化，
+    // 读不到前台窗口的平台不会因此取消
+    pub fn check(
+        &self,
+        now: Instant,
+        idle_timeout: Duration,
+        focus: Option<u64>,
+    ) -> Option<CloseReason> {
+        if !idle_timeout.is_zero() && now.duration_since(self.last_activity) >= idle_timeout {
+            return Some(CloseReason::IdleTimeout);
+        }
+        match (self.focus, focus) {
+            (Some(started), Some(current)) if started != current => Some(CloseReason::FocusChanged),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    #[test]
+    fn expires_after_idle_timeout() {
+        let start = Instant::now();
+        let mut session = OverlaySession::new(1, None, start);
+        assert_eq!(
+            session.check(start + Duration::from_secs(29), TIMEOUT, None),
+            None
+        );
+        assert_eq!(
+            session.check(start + TIMEOUT, TIMEOUT, None),
+            Some(CloseReason::IdleTimeout)
+        );
+
+        // 每次按键重新计时
+        session.touch(start + Duration::from_secs(20));
+        assert_eq!(
+            session.check(start + Duration::from_secs(45), TIMEOUT, None),
+            None
+        );
+
+        let later = start + Duration::from_secs(3600);
+        assert_eq!(session.check(later, Duration::ZERO, None), None);
+    }
+
+    #[test]
+    fn cancels_when_focus_moves() {
+        let start = Instant::now();
+        let session = OverlaySession::new(1, Some(42), start);
+        assert_eq!(session.check(start, TIMEOUT, Some(42)), None);
+        assert_eq!(
+            session.check(start, TIMEOUT, Some(7)),
+            Some(CloseReason::FocusChanged)
+        );
+        // 读不到前台窗口时不取消
+        assert_eq!(session.check(start, TIMEOUT, None), None);
+        let unknown = OverlaySession::new(2, None, start);
+        assert_eq!(unknown.check(start, TIMEOUT, Some(7)), None);
+    }
+}
@@ -1,5 +1,29 @@
 ﻿import { describe, expect, it } from "vitest";
-import { cropRegion, regionCenter } from "../engine";
+import type { Point, Region } from "../types";
+import {
+  cropRegion,
+  gridEdges,
+  overlapRegion,
+  regionCenter,
+} from "../engine";
+
+function contains(region: Region, point: Point): boolean {
+  return (
+    point.x >= region.x &&
+    point.x <= region.x + region.width &&
+    point.y >= region.y &&
+    point.y <= region.y + region.height
+  );
+}
+
+function strictlyContains(region: Region, point: Point): boolean {
+  return (
+    point.x > region.x &&
+    point.x < region.x + region.width &&
+    point.y > region.y &&
+    point.y < region.y + region.height
+  );
+}
 
 describe("cropRegion", () => {
   it("calculates a single-step crop for a 3x3 grid", () => {
@@ -9,6 +33,73 @@ describe("cropRegion", () => {
     expect(next).toEqual({ x: 100, y: 100, width: 100, height: 100 });
   });
 
+  it("sizes bands by row and column weights", () => {
+    const region = { x: 0, y: 0, width: 400, height: 500 };
+
+    expect(cropRegion(region, 3, 2, 1, [1, 1, 3], [3, 1])).toEqual({
+      x: 0,
+      y: 0,
+      width: 300,
+      height: 100,
+    });
+    expect(cropRegion(region, 3, 2, 6, [1, 1, 3], [3, 1])).toEqual({
+      x: 300,
+      y: 200,
+      width: 100,
+      height: 300,
+    });
+  });
+
+  it("falls back to equal bands for mismatched weights", () => {
+    expect(gridEdges(2, [1, 2, 3])).toEqual([0, 0.5, 1]);
+    expect(gridEdges(2, [1, 0])).toEqual([0, 0.5, 1]);
+    expect(gridEdges(4, [1, 1, 1, 1])).toEqual([0, 0.25, 0.5, 0.75, 1]);
+  });
+
+  it("grows a cell by the overlap ratio within the bounds", () => {
+    const bounds = { x: 0, y: 0, width: 1000, height: 1000 };
+
+    expect(
+      overlapRegion({ x: 500, y: 250, width: 100, height: 50 }, 0.1, bounds),
+    ).toEqual({ x: 490, y: 245, width: 120, height: 60 });
+    expect(
+      overlapRegion({ x: 0, y: 900, width: 100, height: 100 }, 0.25, bounds),
+    ).toEqual({ x: 0, y: 875, width: 125, height: 125 });
+  });
+
+  it("keeps a boundary target inside the chosen region on every layer", () => {
+    const monitor = { x: 0, y: 0, width: 1920, height: 1080 };
+    // 目标恰好落在第一层 3x3 网格的交点上
+    const target = { x: 640, y: 360 };
+    const layers = [
+      { rows: 3, cols: 3 },
+      { rows: 2, cols: 4 },
+      { rows: 3, cols: 5 },
+      { rows: 2, cols: 2 },
+    ];
+
+    let region: Region = monitor;
+    for (const layer of layers) {
+      // 总是选择目标所在的第一个格子，即边界上的左上侧
+      const count = layer.rows * layer.cols;
+      let cell: Region | null = null;
+      for (let keyIndex = 1; keyIndex <= count && !cell; keyIndex += 1) {
+        const candidate = cropRegion(region, layer.rows, layer.cols, keyIndex);
+        if (contains(candidate, target)) {
+          cell = candidate;
+        }
+      }
+
+      expect(cell).not.toBeNull();
+      region = overlapRegion(cell!, 0.15, monitor);
+      expect(strictlyContains(region, target)).toBe(true);
+    }
+
+    const center = regionCenter(region);
+    expect(Math.abs(center.x - target.x)).toBeLessThan(region.width / 2);
+    expect(Math.abs(center.y - target.y)).toBeLessThan(region.height / 2);
+  });
+
   it("returns the center point with rounding", () => {
     const region = { x: 10, y: 10, width: 5, height: 5 };
     const center = regionCenter(region);
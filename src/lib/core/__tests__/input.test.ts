@@ -1,6 +1,6 @@
 import { describe, expect, it } from "vitest";
 import type { AppConfig, Region, RuntimeState } from "../types";
-import { applyKey, createInitialState } from "../engine";
+import { applyKey, createInitialState, overlayCssSizes } from "../engine";
 
 function makeConfig(): AppConfig {
   return {
@@ -12,6 +12,8 @@ function makeConfig(): AppConfig {
     hotkeys: {
       activation: {
         trigger: "Ctrl+;",
+        local: "",
+        window: "",
       },
       controls: {
         cancel: "Esc",
@@ -21,9 +23,28 @@ function makeConfig(): AppConfig {
         nextMonitor: "Tab",
       },
     },
+    monitor: {
+      startStrategy: "primary",
+      startName: "",
+      previousKey: "Shift+Tab",
+      jumpKeys: [],
+      directionKeys: { left: "", right: "", up: "", down: "" },
+      picker: false,
+      showAll: false,
+      spanAll: false,
+      useWorkArea: false,
+      margins: [],
+      profiles: [],
+    },
     nudge: {
       stepPx: 5,
     },
+    finish: {
+      autoBelowPx: 0,
+      autoAction: "click",
+      fallbackAbovePx: 0,
+      anchorKeys: [],
+    },
     mouse: {
       smoothMove: true,
       moveDurationMs: 120,
@@ -48,8 +69,10 @@ function makeConfig(): AppConfig {
     layers: [
       {
         mode: "combo",
-        stage0: { rows: 1, cols: 2, keys: ["a", "b"] },
-        stage1: { rows: 2, cols: 1, keys: ["c", "d"] },
+        stages: [
+          { rows: 1, cols: 2, keys: ["a", "b"] },
+          { rows: 2, cols: 1, keys: ["c", "d"] },
+        ],
       },
       {
         mode: "single",
@@ -58,6 +81,14 @@ function makeConfig(): AppConfig {
         keys: ["e", "f"],
       },
     ],
+    local: {
+      widthPx: 320,
+      heightPx: 320,
+      layers: [],
+    },
+    window: {
+      layers: [],
+    },
     overlay: {
       alpha: 120,
       maskColor: "#000000",
@@ -66,6 +97,9 @@ function makeConfig(): AppConfig {
       lineWidthPx: 1,
       showGrid: true,
       showDiagonals: true,
+      scaleWithMonitor: true,
+      idleTimeoutMs: 30000,
+      cancelOnFocusChange: true,
       font: { family: "Segoe UI", sizePx: 12 },
     },
   };
@@ -118,6 +152,248 @@ describe("engine inputs", () => {
     expect(undone.state.history).toHaveLength(0);
   });
 
+  it("walks every stage of a multi-stage combo", () => {
+    const config = makeConfig();
+    config.layers = [
+      {
+        mode: "combo",
+        stages: [
+          { rows: 1, cols: 2, keys: ["a", "b"] },
+          { rows: 2, cols: 1, keys: ["c", "d"] },
+          { rows: 1, cols: 2, keys: ["e", "f"] },
+        ],
+      },
+    ];
+    const initial = createInitialState(config, {
+      x: 0,
+      y: 0,
+      width: 100,
+      height: 100,
+    });
+
+    const first = applyKey(config, initial, "b");
+    const second = applyKey(config, first.state, "d");
+    expect(second.state.stage).toBe(2);
+    expect(second.state.done).toBe(false);
+    expect(second.state.region).toEqual({
+      x: 50,
+      y: 50,
+      width: 50,
+      height: 50,
+    });
+
+    const third = applyKey(config, second.state, "e");
+    expect(third.state.done).toBe(true);
+    expect(third.clickPoint).toEqual({ x: 63, y: 75 });
+  });
+
+  it("halves the region in a bisect layer", () => {
+    const config = makeConfig();
+    config.layers = [
+      {
+        mode: "bisect",
+        left: "h",
+        right: "l",
+        up: "k",
+        down: "ArrowDown",
+        finish: ";",
+      },
+    ];
+    const initial = createInitialState(config, {
+      x: 0,
+      y: 0,
+      width: 100,
+      height: 80,
+    });
+
+    const right = applyKey(config, initial, "l");
+    const down = applyKey(config, right.state, "Down");
+    expect(down.state.region).toEqual({ x: 50, y: 40, width: 50, height: 40 });
+    expect(down.state.stage).toBe(2);
+
+    const undone = applyKey(config, down.state, "Backspace");
+    expect(undone.state.region).toEqual(right.state.region);
+
+    const finished = applyKey(config, down.state, ";");
+    expect(finished.state.done).toBe(true);
+    expect(finished.clickPoint).toEqual({ x: 75, y: 60 });
+  });
+
+  it("grows picked cells by the layer overlap ratio", () => {
+    const config = makeConfig();
+    config.layers = [
+      {
+        mode: "single",
+        rows: 1,
+        cols: 2,
+        keys: ["e", "f"],
+        overlapRatio: 0.25,
+      },
+      { mode: "single", rows: 1, cols: 2, keys: ["e", "f"] },
+    ];
+    const initial = createInitialState(config, {
+      x: 0,
+      y: 0,
+      width: 100,
+      height: 100,
+    });
+
+    const first = applyKey(config, initial, "f");
+    expect(first.state.region).toEqual({
+      x: 37.5,
+      y: 0,
+      width: 62.5,
+      height: 100,
+    });
+
+    const second = applyKey(config, first.state, "e");
+    expect(second.state.done).toBe(true);
+    expect(second.clickPoint).toEqual({ x: 53, y: 50 });
+  });
+
+  it("clicks configured points in a points layer", () => {
+    const config = makeConfig();
+    config.layers = [
+      {
+        mode: "points",
+        points: [
+          { key: "t", name: "tab bar", x: 40, y: 12 },
+          { key: "b", x: 5, y: 6, origin: "absolute" },
+          { key: "d", x: 1, y: 1, monitor: "DP-1" },
+          { key: "h", x: 2, y: 2, monitor: "HDMI-1" },
+        ],
+      },
+    ];
+    const initial = createInitialState(
+      config,
+      { x: 1920, y: 0, width: 1920, height: 1080 },
+      "HDMI-1",
+    );
+
+    const relative = applyKey(config, initial, "T");
+    expect(relative.state.done).toBe(true);
+    expect(relative.clickPoint).toEqual({ x: 1960, y: 12 });
+
+    const absolute = applyKey(config, initial, "b");
+    expect(absolute.clickPoint).toEqual({ x: 5, y: 6 });
+
+    const otherMonitor = applyKey(config, initial, "d");
+    expect(otherMonitor.didAdvance).toBe(false);
+    expect(applyKey(config, initial, "h").clickPoint).toEqual({
+      x: 1922,
+      y: 2,
+    });
+  });
+
+  it("auto-finishes once the region is below the threshold", () => {
+    const config = makeConfig();
+    config.finish.autoBelowPx = 60;
+    const region = { x: 0, y: 0, width: 100, height: 100 };
+
+    const first = applyKey(config, createInitialState(config, region), "a");
+    expect(first.state.done).toBe(false);
+    const clicked = applyKey(config, first.state, "c");
+    expect(clicked.state.done).toBe(true);
+    expect(clicked.clickPoint).toEqual({ x: 25, y: 25 });
+
+    config.finish.autoAction = "nudge";
+    const nudgeOnly = applyKey(config, first.state, "c").state;
+    expect(nudgeOnly.done).toBe(false);
+    expect(nudgeOnly.nudgeOnly).toBe(true);
+    expect(applyKey(config, nudgeOnly, "e").didAdvance).toBe(false);
+
+    const nudged = applyKey(config, nudgeOnly, "Right");
+    expect(nudged.state.region.x).toBe(5);
+    expect(applyKey(config, nudged.state, "Space").clickPoint).toEqual({
+      x: 30,
+      y: 25,
+    });
+
+    const undone = applyKey(config, nudgeOnly, "Backspace");
+    expect(undone.state.nudgeOnly).toBe(false);
+    expect(undone.state.stage).toBe(1);
+  });
+
+  it("runs the fallback layer while the region is still coarse", () => {
+    const config = makeConfig();
+    config.finish.fallbackAbovePx = 20;
+    config.finish.fallbackLayer = {
+      mode: "single",
+      rows: 1,
+      cols: 2,
+      keys: ["g", "h"],
+    };
+    const initial = createInitialState(config, {
+      x: 0,
+      y: 0,
+      width: 100,
+      height: 100,
+    });
+
+    let state = initial;
+    for (const key of ["a", "c", "e"]) {
+      state = applyKey(config, state, key).state;
+    }
+    expect(state.done).toBe(false);
+    expect(state.layerIndex).toBe(2);
+
+    const fallback = applyKey(config, state, "h");
+    expect(fallback.state.done).toBe(true);
+    expect(fallback.clickPoint).toEqual({ x: 19, y: 25 });
+
+    config.finish.fallbackAbovePx = 60;
+    state = initial;
+    for (const key of ["a", "c"]) {
+      state = applyKey(config, state, key).state;
+    }
+    expect(applyKey(config, state, "e").state.done).toBe(true);
+  });
+
+  it("picks a click anchor after the last layer", () => {
+    const config = makeConfig();
+    config.finish.anchorKeys = ["7", "8", "9", "4", "5", "6", "1", "2", "3"];
+    let state = createInitialState(config, {
+      x: 0,
+      y: 0,
+      width: 100,
+      height: 100,
+    });
+    for (const key of ["a", "c"]) {
+      state = applyKey(config, state, key).state;
+    }
+
+    const anchoring = applyKey(config, state, "e");
+    expect(anchoring.clickPoint).toBeUndefined();
+    expect(anchoring.state.anchoring).toBe(true);
+    expect(anchoring.state.region).toEqual({
+      x: 0,
+      y: 0,
+      width: 25,
+      height: 50,
+    });
+    expect(applyKey(config, anchoring.state, "e").didAdvance).toBe(false);
+
+    const corner = applyKey(config, anchoring.state, "3");
+    expect(corner.state.done).toBe(true);
+    expect(corner.clickPoint).toEqual({ x: 24, y: 49 });
+    expect(corner.anchor).toEqual({
+      position: "bottomRight",
+      cell: { x: 0, y: 0, width: 25, height: 50 },
+    });
+    expect(applyKey(config, anchoring.state, "8").clickPoint).toEqual({
+      x: 13,
+      y: 0,
+    });
+
+    const direct = applyKey(config, anchoring.state, "Space");
+    expect(direct.clickPoint).toEqual({ x: 13, y: 25 });
+    expect(direct.anchor).toBeUndefined();
+
+    const undone = applyKey(config, anchoring.state, "Backspace");
+    expect(undone.state.anchoring).toBe(false);
+    expect(undone.state.layerIndex).toBe(1);
+  });
+
   it("nudges region only in single step", () => {
     const config = makeConfig();
     const initial = createInitialState(config, {
@@ -175,4 +451,59 @@ describe("engine inputs", () => {
       height: 20,
     });
   });
+
+  it("keeps spanning selections on a monitor", () => {
+    const config = makeConfig();
+    config.layers = [
+      { mode: "single", rows: 2, cols: 2, keys: ["q", "w", "a", "s"] },
+      { mode: "single", rows: 1, cols: 2, keys: ["j", "k"] },
+    ];
+    // 右屏较矮，外接矩形右下角不在任何屏幕上
+    const areas = [
+      { name: "LEFT", region: { x: 0, y: 0, width: 100, height: 100 } },
+      { name: "RIGHT", region: { x: 100, y: 0, width: 100, height: 40 } },
+    ];
+    const bounds: Region = { x: 0, y: 0, width: 200, height: 100 };
+    const initial = createInitialState(config, bounds, null, areas);
+
+    expect(applyKey(config, initial, "s").didAdvance).toBe(false);
+
+    const picked = applyKey(config, initial, "w");
+    expect(picked.state.region).toEqual({
+      x: 100,
+      y: 0,
+      width: 100,
+      height: 40,
+    });
+    expect(picked.state.baseRegion).toEqual(areas[1].region);
+    expect(picked.state.monitor).toBe("RIGHT");
+
+    const undone = applyKey(config, picked.state, "Backspace");
+    expect(undone.state.baseRegion).toEqual(bounds);
+    expect(undone.state.monitor).toBeNull();
+
+    const direct = applyKey(config, initial, "Space");
+    expect(direct.clickPoint).toEqual({ x: 50, y: 50 });
+  });
+
+  it("pins overlay sizes on a 1.5x monitor", () => {
+    const config = makeConfig();
+    config.overlay.font.sizePx = 14;
+    config.overlay.lineWidthPx = 2;
+    const physical = (sized: AppConfig) => ({
+      font: sized.overlay.font.sizePx * 1.5,
+      line: sized.overlay.lineWidthPx * 1.5,
+    });
+
+    // 默认随显示器缩放：14 CSS 像素在屏幕上占 21 个物理像素
+    expect(physical(overlayCssSizes(config, 1.5))).toEqual({
+      font: 21,
+      line: 3,
+    });
+
+    config.overlay.scaleWithMonitor = false;
+    const fixed = physical(overlayCssSizes(config, 1.5));
+    expect(fixed.font).toBeCloseTo(14);
+    expect(fixed.line).toBeCloseTo(2);
+  });
 });
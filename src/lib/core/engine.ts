@@ -1,36 +1,158 @@
 ﻿import type {
+  AnchorPosition,
   AppConfig,
+  BisectDirection,
+  BisectLayer,
+  ClickAnchor,
   ComboLayer,
   ComboStage,
   CurrentStep,
   EngineOutput,
   GridStage,
   Layer,
+  MonitorArea,
   Point,
+  PointsLayer,
   Region,
+  ResolvedPoint,
   RuntimeState,
   RuntimeSnapshot,
 } from "./types";
 
+// 返回 count 条带的分界位置（0 到 1），权重缺省或长度不符时均分
+export function gridEdges(count: number, weights?: number[]): number[] {
+  const sizes =
+    weights?.length === count &&
+    weights.every((weight) => Number.isFinite(weight) && weight > 0)
+      ? weights
+      : new Array<number>(count).fill(1);
+  const total = sizes.reduce((sum, size) => sum + size, 0);
+  const edges = [0];
+  let covered = 0;
+  for (const size of sizes) {
+    covered += size;
+    edges.push(covered / total);
+  }
+  return edges;
+}
+
 export function cropRegion(
   current: Region,
   rows: number,
   cols: number,
   keyIndex: number,
+  rowWeights?: number[],
+  colWeights?: number[],
 ): Region {
   const row = Math.ceil(keyIndex / cols);
   const col = ((keyIndex - 1) % cols) + 1;
-  const cellWidth = current.width / cols;
-  const cellHeight = current.height / rows;
+  const rowEdges = gridEdges(rows, rowWeights);
+  const colEdges = gridEdges(cols, colWeights);
 
   return {
-    x: current.x + (col - 1) * cellWidth,
-    y: current.y + (row - 1) * cellHeight,
-    width: cellWidth,
-    height: cellHeight,
+    x: current.x + colEdges[col - 1] * current.width,
+    y: current.y + rowEdges[row - 1] * current.height,
+    width: (colEdges[col] - colEdges[col - 1]) * current.width,
+    height: (rowEdges[row] - rowEdges[row - 1]) * current.height,
   };
 }
 
+export function overlapRegion(
+  cell: Region,
+  ratio: number,
+  bounds: Region,
+): Region {
+  if (!(ratio > 0)) {
+    return cell;
+  }
+  const marginX = cell.width * ratio;
+  const marginY = cell.height * ratio;
+  const left = Math.max(bounds.x, cell.x - marginX);
+  const top = Math.max(bounds.y, cell.y - marginY);
+  const right = Math.min(
+    bounds.x + bounds.width,
+    cell.x + cell.width + marginX,
+  );
+  const bottom = Math.min(
+    bounds.y + bounds.height,
+    cell.y + cell.height + marginY,
+  );
+
+  return { x: left, y: top, width: right - left, height: bottom - top };
+}
+
+export function bisectRegion(
+  current: Region,
+  direction: BisectDirection,
+): Region {
+  switch (direction) {
+    case "left":
+      return { ...current, width: current.width / 2 };
+    case "right":
+      return {
+        ...current,
+        x: current.x + current.width / 2,
+        width: current.width / 2,
+      };
+    case "up":
+      return { ...current, height: current.height / 2 };
+    case "down":
+      return {
+        ...current,
+        y: current.y + current.height / 2,
+        height: current.height / 2,
+      };
+  }
+}
+
+export function resolvePoints(
+  layer: PointsLayer,
+  baseRegion: Region,
+  monitor: string | null,
+): ResolvedPoint[] {
+  return layer.points
+    .filter((point) => !point.monitor || point.monitor === monitor)
+    .map((point) => {
+      const absolute = point.origin === "absolute";
+      return {
+        key: point.key,
+        name: point.name ?? "",
+        group: point.group ?? "",
+        x: Math.round(absolute ? point.x : baseRegion.x + point.x),
+        y: Math.round(absolute ? point.y : baseRegion.y + point.y),
+      };
+    });
+}
+
+export function intersectRegion(a: Region, b: Region): Region | null {
+  const left = Math.max(a.x, b.x);
+  const top = Math.max(a.y, b.y);
+  const right = Math.min(a.x + a.width, b.x + b.width);
+  const bottom = Math.min(a.y + a.height, b.y + b.height);
+  if (right <= left || bottom <= top) {
+    return null;
+  }
+  return { x: left, y: top, width: right - left, height: bottom - top };
+}
+
+// 与 region 重叠面积最大的显示器，完全不重叠时为 null
+export function bestArea(
+  region: Region,
+  areas: MonitorArea[],
+): MonitorArea | null {
+  let best: MonitorArea | null = null;
+  let bestSize = 0;
+  for (const area of areas) {
+    const overlap = intersectRegion(region, area.region);
+    const size = overlap ? overlap.width * overlap.height : 0;
+    if (size > bestSize) {
+      best = area;
+      bestSize = size;
+    }
+  }
+  return best;
+}
+
 export function regionCenter(region: Region): Point {
   return {
     x: Math.round(region.x + region.width / 2),
@@ -38,6 +160,66 @@ export function regionCenter(region: Region): Point {
   };
 }
 
+export const ANCHOR_POSITIONS: AnchorPosition[] = [
+  "topLeft",
+  "top",
+  "topRight",
+  "left",
+  "center",
+  "right",
+  "bottomLeft",
+  "bottom",
+  "bottomRight",
+];
+
+// 格子覆盖的像素为 [ceil(start), ceil(start + length) - 1]，与原生端一致
+function anchorCoordinate(
+  start: number,
+  length: number,
+  side: number,
+): number {
+  const first = Math.ceil(start);
+  const last = Math.max(first, Math.ceil(start + length) - 1);
+  if (side === 0) {
+    return first;
+  }
+  if (side === 2) {
+    return last;
+  }
+  return clamp(Math.round(start + length / 2), first, last);
+}
+
+export function anchorPoint(region: Region, position: AnchorPosition): Point {
+  const index = ANCHOR_POSITIONS.indexOf(position);
+  return {
+    x: anchorCoordinate(region.x, region.width, index % 3),
+    y: anchorCoordinate(region.y, region.height, Math.floor(index / 3)),
+  };
+}
+
+// 字号与线宽按 CSS 像素配置，画布已按 devicePixelRatio 放大；
+// 关闭 scaleWithMonitor 时换算回固定的物理像素
+export function overlayCssSizes(config: AppConfig, scale: number): AppConfig {
+  if (config.overlay.scaleWithMonitor || !(scale > 0)) {
+    return config;
+  }
+  return {
+    ...config,
+    overlay: {
+      ...config.overlay,
+      lineWidthPx: config.overlay.lineWidthPx / scale,
+      font: {
+        ...config.overlay.font,
+        sizePx: config.overlay.font.sizePx / scale,
+      },
+    },
+  };
+}
+
+function anchorEnabled(config: AppConfig): boolean {
+  return config.finish.anchorKeys?.length === ANCHOR_POSITIONS.length;
+}
+
 const KEY_ALIASES: Record<string, string> = {
   escape: "esc",
   esc: "esc",
@@ -51,7 +233,7 @@ const KEY_ALIASES: Record<string, string> = {
   arrowdown: "down",
 };
 
-function normalizeKey(value: string): string {
+export function normalizeKey(value: string): string {
   const lower = value.toLowerCase();
   return KEY_ALIASES[lower] ?? lower;
 }
@@ -118,19 +300,47 @@ function pushHistory(state: RuntimeState): RuntimeSnapshot[] {
       layerIndex: state.layerIndex,
       stage: state.stage,
       region: { ...state.region },
+      baseRegion: { ...state.baseRegion },
+      monitor: state.monitor,
     },
   ];
 }
 
+// 跨屏时裁剪到重叠最多的显示器，后续层只在这块屏内进行
+function settleInAreas(
+  state: RuntimeState,
+  region: Region,
+): Pick<RuntimeState, "region" | "baseRegion" | "monitor"> | null {
+  if (!state.areas.length) {
+    return { region, baseRegion: state.baseRegion, monitor: state.monitor };
+  }
+  const area = bestArea(region, state.areas);
+  const cropped = area && intersectRegion(region, area.region);
+  if (!area || !cropped) {
+    return null;
+  }
+  return {
+    region: cropped,
+    baseRegion: { ...area.region },
+    monitor: area.name ?? state.monitor,
+  };
+}
+
 export function createInitialState(
   config: AppConfig,
   initialRegion: Region,
+  monitor: string | null = null,
+  areas: MonitorArea[] = [],
 ): RuntimeState {
   return {
     layerIndex: 0,
     stage: 0,
     region: { ...initialRegion },
     baseRegion: { ...initialRegion },
+    monitor,
+    areas,
+    nudgeOnly: false,
+    anchoring: false,
     done: config.layers.length === 0,
     history: [],
   };
@@ -161,8 +371,12 @@ export function applyKey(
     const nextState = {
       ...state,
       region: { ...previous.region },
+      baseRegion: { ...previous.baseRegion },
+      monitor: previous.monitor,
       layerIndex: previous.layerIndex,
       stage: previous.stage,
+      nudgeOnly: false,
+      anchoring: false,
       done: false,
       history: state.history.slice(0, -1),
     };
@@ -171,14 +385,38 @@ export function applyKey(
   }
 
   if (normalizedKey === controlKeys.directClick) {
+    // 跨屏区域的中心可能不在任何屏幕上
+    const target = settleInAreas(state, state.region)?.region ?? state.region;
     return {
       state: { ...state, done: true },
-      clickPoint: regionCenter(state.region),
+      clickPoint: regionCenter(target),
       didAdvance: true,
     };
   }
 
+  if (state.anchoring) {
+    return applyAnchorKey(config, state, normalizedKey);
+  }
+
   const nudge = getNudgeDelta(normalizedKey, resolveNudgeStep(config));
+  if (state.nudgeOnly) {
+    // 自动结束后只接受方向键微调，由 directClick 点击
+    const nextRegion = nudge
+      ? nudgeRegion(state.region, state.baseRegion, nudge)
+      : state.region;
+    return regionsEqual(nextRegion, state.region)
+      ? { state, didAdvance: false }
+      : { state: { ...state, region: nextRegion }, didAdvance: true };
+  }
+
+  const currentLayer = layerAt(config, state.layerIndex);
+  if (currentLayer?.mode === "bisect") {
+    return applyBisectKey(config, state, currentLayer, normalizedKey);
+  }
+  if (currentLayer?.mode === "points") {
+    return applyPointsKey(state, currentLayer, normalizedKey);
+  }
+
   if (nudge) {
     const step = getCurrentStep(config, state);
     if (!step || step.mode !== "single") {
@@ -193,7 +431,7 @@ export function applyKey(
     return { state: { ...state, region: nextRegion }, didAdvance: true };
   }
 
-  const layer = config.layers[state.layerIndex];
+  const layer = layerAt(config, state.layerIndex);
   if (!layer) {
     return { state: { ...state, done: true }, didAdvance: false };
   }
@@ -210,25 +448,43 @@ export function applyKey(
     return { state, didAdvance: false };
   }
 
-  const nextRegion = cropRegion(
-    state.region,
-    step.rows,
-    step.cols,
-    keyIndex + 1,
+  const settled = settleInAreas(
+    state,
+    cropRegion(
+      state.region,
+      step.rows,
+      step.cols,
+      keyIndex + 1,
+      step.rowWeights,
+      step.colWeights,
+    ),
   );
+  if (!settled) {
+    return { state, didAdvance: false };
+  }
+  const cell = settled.region;
+  const overlapRatio =
+    layer.mode === "single" || layer.mode === "combo"
+      ? (layer.overlapRatio ?? 0)
+      : 0;
+  const nextRegion = overlapRegion(cell, overlapRatio, settled.baseRegion);
   const history = pushHistory(state);
   const nextState = advanceState(
-    state,
-    config.layers.length,
+    config,
+    { ...state, ...settled },
     layer,
     nextRegion,
     history,
   );
 
+  // 点击与锚点都基于选中的格子，扩展只影响后续层可达的范围
+  if (nextState.anchoring) {
+    return { state: { ...nextState, region: cell }, didAdvance: true };
+  }
   if (nextState.done) {
     return {
       state: nextState,
-      clickPoint: regionCenter(nextRegion),
+      clickPoint: regionCenter(cell),
       didAdvance: true,
     };
   }
@@ -236,11 +492,137 @@ export function applyKey(
   return { state: nextState, didAdvance: true };
 }
 
+function applyAnchorKey(
+  config: AppConfig,
+  state: RuntimeState,
+  normalizedKey: string,
+): EngineOutput {
+  const index = config.finish.anchorKeys.findIndex(
+    (candidate) => normalizeKey(candidate) === normalizedKey,
+  );
+  if (index < 0) {
+    return { state, didAdvance: false };
+  }
+
+  const anchor: ClickAnchor = {
+    position: ANCHOR_POSITIONS[index],
+    cell: { ...state.region },
+  };
+  return {
+    state: {
+      ...state,
+      anchoring: false,
+      done: true,
+      history: pushHistory(state),
+    },
+    clickPoint: anchorPoint(state.region, anchor.position),
+    anchor,
+    didAdvance: true,
+  };
+}
+
+function getBisectDirection(
+  layer: BisectLayer,
+  normalizedKey: string,
+): BisectDirection | null {
+  const directions: BisectDirection[] = ["left", "right", "up", "down"];
+  return (
+    directions.find(
+      (direction) => normalizeKey(layer[direction]) === normalizedKey,
+    ) ?? null
+  );
+}
+
+function applyBisectKey(
+  config: AppConfig,
+  state: RuntimeState,
+  layer: BisectLayer,
+  normalizedKey: string,
+): EngineOutput {
+  const history = pushHistory(state);
+
+  if (normalizedKey === normalizeKey(layer.finish)) {
+    const settled = settleInAreas(state, state.region);
+    if (!settled) {
+      return { state, didAdvance: false };
+    }
+    const nextState = advanceState(
+      config,
+      { ...state, ...settled },
+      layer,
+      settled.region,
+      history,
+    );
+    return nextState.done
+      ? {
+          state: nextState,
+          clickPoint: regionCenter(settled.region),
+          didAdvance: true,
+        }
+      : { state: nextState, didAdvance: true };
+  }
+
+  const direction = getBisectDirection(layer, normalizedKey);
+  if (!direction) {
+    return { state, didAdvance: false };
+  }
+
+  // 已经细到 1px 的方向不再对半
+  const horizontal = direction === "left" || direction === "right";
+  const span = horizontal ? state.region.width : state.region.height;
+  if (span < 2) {
+    return { state, didAdvance: false };
+  }
+
+  const settled = settleInAreas(state, bisectRegion(state.region, direction));
+  if (!settled) {
+    return { state, didAdvance: false };
+  }
+
+  return {
+    state: {
+      ...state,
+      ...settled,
+      stage: state.stage + 1,
+      history,
+    },
+    didAdvance: true,
+  };
+}
+
+// 定点层按键直接点击对应位置，不再进入后续层
+function applyPointsKey(
+  state: RuntimeState,
+  layer: PointsLayer,
+  normalizedKey: string,
+): EngineOutput {
+  const point = resolvePoints(layer, state.baseRegion, state.monitor).find(
+    (candidate) => normalizeKey(candidate.key) === normalizedKey,
+  );
+  if (!point) {
+    return { state, didAdvance: false };
+  }
+
+  return {
+    state: {
+      ...state,
+      region: { x: point.x, y: point.y, width: 1, height: 1 },
+      done: true,
+      history: pushHistory(state),
+    },
+    clickPoint: { x: point.x, y: point.y },
+    didAdvance: true,
+  };
+}
+
 export function getCurrentStep(
   config: AppConfig,
   state: RuntimeState,
 ): CurrentStep | null {
-  const layer = config.layers[state.layerIndex];
+  if (state.nudgeOnly || state.anchoring) {
+    return null;
+  }
+  const layer = layerAt(config, state.layerIndex);
   if (!layer) {
     return null;
   }
@@ -257,6 +639,8 @@ export function getCurrentStep(
     rows: step.rows,
     cols: step.cols,
     keys: step.keys,
+    rowWeights: step.rowWeights,
+    colWeights: step.colWeights,
   };
 }
 
@@ -266,15 +650,65 @@ function getStepForLayer(layer: Layer, stage: ComboStage): GridStage | null {
       rows: layer.rows,
       cols: layer.cols,
       keys: layer.keys,
+      rowWeights: layer.rowWeights,
+      colWeights: layer.colWeights,
+    };
+  }
+  if (layer.mode === "bisect") {
+    // 十字分成四半，标签按 3x3 摆放：上、左、确认、右、下
+    return {
+      rows: 3,
+      cols: 3,
+      keys: [
+        "",
+        layer.up,
+        "",
+        layer.left,
+        layer.finish,
+        layer.right,
+        "",
+        layer.down,
+        "",
+      ],
     };
   }
 
-  return stage === 0 ? layer.stage0 : layer.stage1;
+  if (layer.mode === "points") {
+    // 标签画在各点位置上，不使用网格
+    return { rows: 1, cols: 1, keys: [] };
+  }
+
+  return layer.stages[stage] ?? null;
+}
+
+// 所有层之后可追加一个兜底层，序号为 config.layers.length
+export function layerAt(config: AppConfig, index: number): Layer | null {
+  if (index < config.layers.length) {
+    return config.layers[index] ?? null;
+  }
+  if (index === config.layers.length) {
+    return config.finish.fallbackLayer ?? null;
+  }
+  return null;
+}
+
+function needsFallback(config: AppConfig, region: Region): boolean {
+  const threshold = config.finish.fallbackAbovePx;
+  return (
+    threshold > 0 &&
+    !!config.finish.fallbackLayer &&
+    (region.width > threshold || region.height > threshold)
+  );
+}
+
+function belowAutoFinish(config: AppConfig, region: Region): boolean {
+  const threshold = config.finish.autoBelowPx;
+  return threshold > 0 && region.width < threshold && region.height < threshold;
 }
 
 function advanceState(
+  config: AppConfig,
   state: RuntimeState,
-  layersLength: number,
   layer: Layer,
   nextRegion: Region,
   history: RuntimeSnapshot[],
@@ -283,8 +717,8 @@ function advanceState(
   let nextStage: ComboStage = 0;
 
   if (layer.mode === "combo") {
-    if (state.stage === 0) {
-      nextStage = 1;
+    if (state.stage + 1 < layer.stages.length) {
+      nextStage = state.stage + 1;
     } else {
       nextLayerIndex += 1;
       nextStage = 0;
@@ -294,13 +728,28 @@ function advanceState(
     nextStage = 0;
   }
 
-  const done = nextLayerIndex >= layersLength;
+  const layersLength = config.layers.length;
+  let done =
+    nextLayerIndex > layersLength ||
+    (nextLayerIndex === layersLength && !needsFallback(config, nextRegion));
+  let nudgeOnly = false;
+  if (!done && belowAutoFinish(config, nextRegion)) {
+    done = config.finish.autoAction !== "nudge";
+    nudgeOnly = !done;
+  }
+  // 原本要点击时先进入锚点步骤
+  const anchoring = done && anchorEnabled(config);
+  if (anchoring) {
+    done = false;
+  }
 
   return {
     ...state,
     region: nextRegion,
     layerIndex: nextLayerIndex,
     stage: nextStage,
+    nudgeOnly,
+    anchoring,
     done,
     history,
   };
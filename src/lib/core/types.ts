@@ -7,31 +7,118 @@ export interface Region {
   height: number;
 }
 
+// 不指定 monitor 的条目适用于所有显示器
+export interface MonitorMargins {
+  monitor?: string;
+  top: number;
+  right: number;
+  bottom: number;
+  left: number;
+}
+
+// 按显示器名称或分辨率（如 3440x1440）覆盖层与遮罩尺寸
+export interface MonitorProfile {
+  monitor: string;
+  // 空数组沿用 layers
+  layers: Layer[];
+  // CSS 像素，与全局字号、线宽一样按 scaleWithMonitor 缩放
+  fontSizePx?: number;
+  lineWidthPx?: number;
+}
+
+export interface MonitorArea {
+  name: string | null;
+  region: Region;
+}
+
 export interface Point {
   x: number;
   y: number;
 }
 
+// 权重为各行/列的相对尺寸，缺省时均分
 export interface GridStage {
   rows: number;
   cols: number;
   keys: KeyCode[];
+  rowWeights?: number[];
+  colWeights?: number[];
 }
 
-export interface SingleLayer {
+// overlapRatio：选中的格子向四周扩展的比例，扩展后不超出显示器
+export interface SingleLayer extends GridStage {
   mode: "single";
-  rows: number;
-  cols: number;
-  keys: KeyCode[];
+  overlapRatio?: number;
 }
 
 export interface ComboLayer {
   mode: "combo";
-  stage0: GridStage;
-  stage1: GridStage;
+  stages: GridStage[];
+  overlapRatio?: number;
+}
+
+export interface BisectLayer {
+  mode: "bisect";
+  left: KeyCode;
+  right: KeyCode;
+  up: KeyCode;
+  down: KeyCode;
+  finish: KeyCode;
+}
+
+export type PointOrigin = "absolute" | "monitor";
+
+export interface HintPoint {
+  key: KeyCode;
+  name?: string;
+  x: number;
+  y: number;
+  origin?: PointOrigin;
+  monitor?: string;
+  group?: string;
+}
+
+export interface PointsLayer {
+  mode: "points";
+  points: HintPoint[];
 }
 
-export type Layer = SingleLayer | ComboLayer;
+export type Layer = SingleLayer | ComboLayer | BisectLayer | PointsLayer;
+
+// 已换算为屏幕物理坐标的定点
+export interface ResolvedPoint extends Point {
+  key: KeyCode;
+  name: string;
+  group: string;
+}
+
+export type AutoFinishAction = "click" | "nudge";
+
+export type StartStrategy =
+  | "primary"
+  | "cursor"
+  | "focusedWindow"
+  | "lastUsed"
+  | "named";
+
+// 按小键盘顺序排列的九个落点
+export type AnchorPosition =
+  | "topLeft"
+  | "top"
+  | "topRight"
+  | "left"
+  | "center"
+  | "right"
+  | "bottomLeft"
+  | "bottom"
+  | "bottomRight";
+
+export interface ClickAnchor {
+  position: AnchorPosition;
+  cell: Region;
+}
+
+export type BisectDirection = "left" | "right" | "up" | "down";
 
 export interface AppConfig {
   app: {
@@ -46,6 +133,10 @@ export interface AppConfig {
   hotkeys: {
     activation: {
       trigger: string;
+      // 在光标附近打开小网格，空字符串为关闭
+      local: string;
+      // 以前台窗口为初始区域，空字符串为关闭
+      window: string;
     };
     controls: {
       cancel: string;
@@ -55,9 +146,45 @@ export interface AppConfig {
       nextMonitor: string;
     };
   };
+  monitor: {
+    // 激活时打开哪块显示器，无法确定时回到主显示器
+    startStrategy: StartStrategy;
+    startName: string;
+    // 反向切换，与 nextMonitor 对应
+    previousKey: string;
+    // 第 N 个键直接跳到第 N 块显示器
+    jumpKeys: string[];
+    // 按屏幕位置切换到相邻显示器，空字符串为关闭
+    directionKeys: {
+      left: string;
+      right: string;
+      up: string;
+      down: string;
+    };
+    // 多块显示器时先用 jumpKeys 选择显示器
+    picker: boolean;
+    // 多块显示器时每块屏同时显示网格，按 jumpKeys 选择显示器
+    showAll: boolean;
+    // 多块显示器时第一层覆盖所有显示器的外接矩形
+    spanAll: boolean;
+    // 使用工作区，避开任务栏与面板
+    useWorkArea: boolean;
+    margins: MonitorMargins[];
+    profiles: MonitorProfile[];
+  };
   nudge: {
     stepPx: number;
   };
+  finish: {
+    // 区域两边都小于该值时自动结束，0 为关闭
+    autoBelowPx: number;
+    autoAction: AutoFinishAction;
+    // 最后一层后仍有一边大于该值时进入兜底层，0 为关闭
+    fallbackAbovePx: number;
+    fallbackLayer?: Layer;
+    // 最后一层后按 3x3 键选择落点，空数组为关闭
+    anchorKeys: KeyCode[];
+  };
   mouse: {
     smoothMove: boolean;
     moveDurationMs: number;
@@ -80,6 +207,16 @@ export interface AppConfig {
     maxStepSleepMs: number;
   };
   layers: Layer[];
+  // 光标附近模式：以鼠标为中心的区域及其单独的层
+  local: {
+    widthPx: number;
+    heightPx: number;
+    layers: Layer[];
+  };
+  // 前台窗口模式的层，空数组时沿用 layers
+  window: {
+    layers: Layer[];
+  };
   overlay: {
     alpha: number;
     maskColor: string;
@@ -88,6 +225,12 @@ export interface AppConfig {
     lineWidthPx: number;
     showGrid: boolean;
     showDiagonals: boolean;
+    // 开启时字号与线宽按 CSS 像素随缩放比例放大，关闭时按物理像素绘制
+    scaleWithMonitor: boolean;
+    // 超过该时长未按覆盖层按键就关闭，0 为关闭
+    idleTimeoutMs: number;
+    // 其他窗口获得焦点时关闭
+    cancelOnFocusChange: boolean;
     font: {
       family: string;
       sizePx: number;
@@ -95,13 +238,22 @@ export interface AppConfig {
   };
 }
 
-export type ComboStage = 0 | 1;
+// 组合层内的阶段序号；二分层记录已对半的次数
+export type ComboStage = number;
 
 export interface RuntimeState {
   layerIndex: number;
   stage: ComboStage;
   region: Region;
   baseRegion: Region;
+  // 当前显示器名称，定点层据此筛选按显示器分组的点
+  monitor: string | null;
+  // 跨屏模式下的各显示器；非空时落在所有显示器之外的格子不可选
+  areas: MonitorArea[];
+  // 自动结束后只允许微调，等待 directClick
+  nudgeOnly: boolean;
+  // 最后一层已选定格子，等待锚点键
+  anchoring: boolean;
   done: boolean;
   history: RuntimeSnapshot[];
 }
@@ -109,6 +261,7 @@ export interface RuntimeState {
 export interface EngineOutput {
   state: RuntimeState;
   clickPoint?: Point;
+  anchor?: ClickAnchor;
   didAdvance: boolean;
 }
 
@@ -116,13 +269,17 @@ export interface RuntimeSnapshot {
   layerIndex: number;
   stage: ComboStage;
   region: Region;
+  baseRegion: Region;
+  monitor: string | null;
 }
 
 export interface CurrentStep {
-  mode: "single" | "combo";
+  mode: Layer["mode"];
   stage: ComboStage;
   layerIndex: number;
   rows: number;
   cols: number;
   keys: KeyCode[];
+  rowWeights?: number[];
+  colWeights?: number[];
 }
@@ -15,9 +15,17 @@ const zh = {
   "app.resetting": "正在恢复...",
   "app.reset": "恢复默认",
   "app.importing": "正在导入...",
-  "app.import": "导入 JSON",
+  "app.import": "导入配置",
   "app.exporting": "正在导出...",
-  "app.export": "导出 JSON",
+  "app.export": "导出配置",
+  "app.exportFormat": "导出格式",
+  "app.exportSchema": "导出 Schema",
+  "import.mode": "导入方式",
+  "import.mode.replace": "替换（基于默认）",
+  "import.mode.merge": "合并到当前",
+  "import.mode.patch": "JSON Patch",
+  "import.previewConfirm": "导入将修改 {count} 项，确认应用？",
+  "import.previewMore": "……另有 {count} 项",
   "language.label": "语言",
   "language.zh": "简体中文",
   "language.en": "English",
@@ -25,6 +33,10 @@ const zh = {
   "status.reset": "已恢复默认",
   "status.imported": "已导入",
   "status.exported": "已导出",
+  "status.reloaded": "已从配置文件重新加载",
+  "status.schemaExported": "Schema 已导出",
+  "status.importNoChanges": "导入内容与当前配置一致",
+  "status.layoutGenerated": "已生成 {count} 层，最终格子 {width} × {height} px",
   "presets.section": "预设",
   "presets.title": "预设管理",
   "presets.subtitle": "切换 / 复制 / 重命名",
@@ -42,11 +54,46 @@ const zh = {
   "layers.section": "层",
   "layers.title": "层编辑",
   "layers.subtitle": "行 / 列 / 键位",
+  "layers.conflicts": "键位冲突",
+  "layers.warnings": "键位提示",
+  "layers.generator.keyboard": "键盘布局",
+  "layers.generator.hands": "用手",
+  "layers.generator.hands.split": "双手",
+  "layers.generator.hands.left": "左手",
+  "layers.generator.hands.right": "右手",
+  "layers.generator.cellPx": "目标格子 (px)",
+  "layers.generator.generate": "生成层级",
+  "layers.precision.measure": "计算精度",
+  "layers.precision.summary":
+    "最终格子 {physical} 物理 px / {logical} 逻辑 px，共 {keystrokes} 次按键",
+  "layers.precision.subPixel": "小于 1px",
+  "layers.precision.coarse": "格子过大",
   "layers.addSingle": "添加单层",
   "layers.addCombo": "添加组合",
   "layers.layerLabel": "第 {index} 层",
   "layers.type.single": "单层",
   "layers.type.combo": "组合",
+  "layers.type.bisect": "二分",
+  "layers.bisectHint": "每按一次方向键保留对应的一半区域，按确认键结束本层。",
+  "layers.bisect.left": "左半",
+  "layers.bisect.right": "右半",
+  "layers.bisect.up": "上半",
+  "layers.bisect.down": "下半",
+  "layers.bisect.finish": "确认",
+  "layers.type.points": "定点",
+  "layers.pointsHint":
+    "每个按键直接点击一个固定位置。相对显示器的坐标从显示器左上角算起；填写显示器名称后只在该显示器上生效。",
+  "layers.point.key": "按键",
+  "layers.point.name": "名称",
+  "layers.point.x": "X（物理像素）",
+  "layers.point.y": "Y（物理像素）",
+  "layers.point.origin": "坐标基准",
+  "layers.point.monitor": "显示器",
+  "layers.point.group": "分组",
+  "layers.pointOrigin.monitor": "相对显示器",
+  "layers.pointOrigin.absolute": "绝对坐标",
+  "layers.addPoint": "添加定点",
+  "layers.removePoint": "删除",
   "layers.mode": "模式",
   "layers.moveUp": "上移",
   "layers.moveDown": "下移",
@@ -58,14 +105,25 @@ const zh = {
   "layers.keysHint": "键位（空格或逗号分隔）",
   "layers.autoFit": "自动补齐",
   "layers.currentExpected": "当前：{current} / 期望：{expected}",
-  "layers.stage0": "阶段 0",
-  "layers.stage1": "阶段 1",
+  "layers.stage": "阶段 {stage}",
+  "layers.addStage": "添加阶段",
+  "layers.removeStage": "删除阶段",
+  "layers.rowWeights": "行权重",
+  "layers.colWeights": "列权重",
+  "layers.weightsPlaceholder": "留空均分，如 1 1 3",
+  "layers.overlapRatio": "格子重叠比例",
   "hotkeys.section": "热键",
   "hotkeys.title": "按键绑定",
   "hotkeys.subtitle": "全局快捷键格式",
   "hotkeys.activation": "激活",
   "hotkeys.controls": "控制",
   "hotkeys.trigger": "激活",
+  "hotkeys.local": "光标附近激活",
+  "hotkeys.localPlaceholder": "留空关闭，如 Ctrl+'",
+  "hotkeys.window": "前台窗口激活",
+  "hotkeys.windowPlaceholder": "留空关闭，如 Ctrl+Shift+;",
+  "hotkeys.windowCustom":
+    "前台窗口模式共 {count} 层，请在配置文件中编辑 window.layers。",
   "hotkeys.leftClick": "左键",
   "hotkeys.rightClick": "右键",
   "hotkeys.middleClick": "中键",
@@ -74,10 +132,62 @@ const zh = {
   "hotkeys.cancel": "取消",
   "hotkeys.undo": "撤销",
   "hotkeys.directClick": "直接点击",
+  "monitor.section": "显示器",
+  "monitor.title": "起始显示器",
+  "monitor.subtitle": "激活时先打开哪块屏幕",
+  "monitor.startStrategy": "起始策略",
+  "monitor.strategy.primary": "主显示器",
+  "monitor.strategy.cursor": "鼠标所在的显示器",
+  "monitor.strategy.focusedWindow": "当前窗口所在的显示器",
+  "monitor.strategy.lastUsed": "上次使用的显示器",
+  "monitor.strategy.named": "按名称指定",
+  "monitor.startName": "显示器名称",
+  "monitor.previousKey": "上一个显示器",
+  "monitor.jumpKeys": "直接跳转按键",
+  "monitor.jumpPlaceholder": "按显示器顺序，例如 F1 F2 F3",
+  "monitor.direction.left": "左侧显示器",
+  "monitor.direction.right": "右侧显示器",
+  "monitor.direction.up": "上方显示器",
+  "monitor.direction.down": "下方显示器",
+  "monitor.picker": "激活时先选择显示器",
+  "monitor.spanAll": "第一层覆盖所有显示器",
+  "monitor.showAll": "所有显示器同时显示网格",
+  "monitor.useWorkArea": "避开任务栏（使用工作区）",
+  "monitor.profiles":
+    "已为 {count} 块显示器单独设置层与遮罩尺寸，请在配置文件中编辑 monitor.profiles。",
+  "monitor.detected": "已连接 {count} 块显示器",
+  "monitor.unnamed": "未命名",
+  "monitor.primary": "主显示器",
+  "monitor.geometry": "{rect}，缩放 {scale}x",
+  "monitor.areas": "工作区 {workArea}；覆盖层 {usable}",
+  "monitor.margin.top": "上边距 (px)",
+  "monitor.margin.right": "右边距 (px)",
+  "monitor.margin.bottom": "下边距 (px)",
+  "monitor.margin.left": "左边距 (px)",
   "nudge.section": "微调",
   "nudge.title": "微调设置",
   "nudge.subtitle": "方向键步长",
   "nudge.step": "步长 (px)",
+  "finish.section": "结束",
+  "finish.title": "自动结束与兜底层",
+  "finish.subtitle": "按区域大小提前结束或追加一层",
+  "finish.autoBelowPx": "区域小于此值时自动结束 (物理 px，0 关闭)",
+  "finish.autoAction": "自动结束后",
+  "finish.action.click": "直接点击中心",
+  "finish.action.nudge": "仅微调，按直接点击键确认",
+  "finish.fallbackAbovePx":
+    "最后一层后仍大于此值时追加兜底层 (物理 px，0 关闭)",
+  "finish.fallbackKeys": "兜底层按键",
+  "finish.fallbackCustom": "兜底层为{mode}模式，请在配置文件中编辑。",
+  "finish.anchorKeys": "落点锚点按键 (按小键盘顺序 9 个，留空关闭)",
+  "finish.anchorPlaceholder": "q w e a s d z x c",
+  "local.section": "局部",
+  "local.title": "光标附近模式",
+  "local.subtitle": "以鼠标为中心的小区域，使用单独的层",
+  "local.widthPx": "区域宽度 (物理 px)",
+  "local.heightPx": "区域高度 (物理 px)",
+  "local.keys": "局部层按键 ({rows}x{cols})",
+  "local.custom": "局部模式共 {count} 层，请在配置文件中编辑 local.layers。",
   "mouse.section": "鼠标",
   "mouse.title": "鼠标行为",
   "mouse.subtitle": "移动轨迹 / 随机性 / 落点",
@@ -108,11 +218,18 @@ const zh = {
   "overlay.fontSize": "字体大小",
   "overlay.showGrid": "显示网格线",
   "overlay.showDiagonals": "显示对角线",
+  "overlay.scaleWithMonitor": "按显示器缩放字号与线宽",
+  "overlay.idleTimeout": "空闲自动关闭 (ms，0 为关闭)",
+  "overlay.cancelOnFocusChange": "切换窗口时自动关闭",
   "overlay.maskColor": "遮罩颜色",
   "overlay.lineColor": "线条颜色",
   "overlay.textColor": "文字颜色",
   "overlay.fontFamily": "字体",
   "overlay.actionHint": "动作：{action}（{key} 切换）",
+  "overlay.nudgeHint": "方向键微调，{key} 点击",
+  "overlay.anchorHint": "选择点击的角、边或中心，{key} 点击中心",
+  "overlay.pickerHint": "按键选择显示器，{key} 取消",
+  "overlay.prefixHint": "按 {key} 在此显示器继续",
   "overlay.action.left": "左键",
   "overlay.action.right": "右键",
   "overlay.action.middle": "中键",
@@ -177,14 +294,29 @@ const zh = {
   "errors.mouseMaxStepSleepMs": "鼠标 maxStepSleepMs 必须大于 0。",
   "errors.overlayLineWidth": "遮罩线宽必须大于 0。",
   "errors.overlayFontSize": "遮罩字体大小必须大于 0。",
+  "errors.reloadFailed": "配置重新加载失败，保留当前配置：{message}",
   "errors.layerGridInvalidSimple": "第 {index} 层网格无效。",
   "errors.layerExpectedKeysSimple": "第 {index} 层需要 {expected} 个键位。",
-  "errors.stage0GridInvalidSimple": "第 {index} 层阶段 0 网格无效。",
-  "errors.stage1GridInvalidSimple": "第 {index} 层阶段 1 网格无效。",
-  "errors.stage0ExpectedKeysSimple":
-    "第 {index} 层阶段 0 需要 {expected} 个键位。",
-  "errors.stage1ExpectedKeysSimple":
-    "第 {index} 层阶段 1 需要 {expected} 个键位。",
+  "errors.stageGridInvalidSimple": "第 {index} 层阶段 {stage} 网格无效。",
+  "errors.stageExpectedKeysSimple":
+    "第 {index} 层阶段 {stage} 需要 {expected} 个键位。",
+  "errors.stagesRequired": "第 {index} 层至少需要一个阶段。",
+  "errors.fallbackLayerInvalid": "兜底层需要 {expected} 个按键。",
+  "errors.anchorKeysInvalid": "落点锚点需要 9 个非空按键。",
+  "errors.startNameEmpty": "按名称指定起始显示器时需填写名称。",
+  "errors.pickerNeedsJumpKeys": "选择显示器需要先设置直接跳转按键。",
+  "errors.showAllNeedsJumpKeys": "同时显示所有显示器需要先设置直接跳转按键。",
+  "errors.localInvalid": "光标附近模式的区域需大于 0，且按键数与网格一致。",
+  "errors.overlapRatioInvalid":
+    "第 {index} 层的重叠比例需在 0 到 {max} 之间。",
+  "errors.layerWeightsInvalid":
+    "第 {index} 层的权重数量需与行列数一致，且均大于 0。",
+  "errors.stageWeightsInvalid":
+    "第 {index} 层阶段 {stage} 的权重数量需与行列数一致，且均大于 0。",
+  "errors.bisectKeysEmpty": "第 {index} 层的二分键位不能为空。",
+  "errors.pointsRequired": "第 {index} 层至少需要一个定点。",
+  "errors.pointKeyEmpty": "第 {index} 层第 {point} 个定点的按键不能为空。",
+  "errors.pointPositionInvalid": "第 {index} 层第 {point} 个定点的坐标无效。",
 } as const;
 
 type TranslationKey = keyof typeof zh;
@@ -199,9 +331,17 @@ const en: Record<TranslationKey, string> = {
   "app.resetting": "Resetting...",
   "app.reset": "Reset to default",
   "app.importing": "Importing...",
-  "app.import": "Import JSON",
+  "app.import": "Import",
   "app.exporting": "Exporting...",
-  "app.export": "Export JSON",
+  "app.export": "Export",
+  "app.exportFormat": "Export format",
+  "app.exportSchema": "Export schema",
+  "import.mode": "Import mode",
+  "import.mode.replace": "Replace (from defaults)",
+  "import.mode.merge": "Merge onto current",
+  "import.mode.patch": "JSON Patch",
+  "import.previewConfirm": "This import changes {count} values. Apply?",
+  "import.previewMore": "…and {count} more",
   "language.label": "Language",
   "language.zh": "简体中文",
   "language.en": "English",
@@ -209,6 +349,11 @@ const en: Record<TranslationKey, string> = {
   "status.reset": "Reset to default",
   "status.imported": "Imported",
   "status.exported": "Exported",
+  "status.reloaded": "Reloaded from override file",
+  "status.schemaExported": "Schema exported",
+  "status.importNoChanges": "Import matches the current config",
+  "status.layoutGenerated":
+    "Generated {count} layers, final cell {width} × {height} px",
   "presets.section": "Presets",
   "presets.title": "Preset Management",
   "presets.subtitle": "Switch, duplicate, rename",
@@ -226,11 +371,47 @@ const en: Record<TranslationKey, string> = {
   "layers.section": "Layers",
   "layers.title": "Layer Editor",
   "layers.subtitle": "Rows, columns, keys",
+  "layers.conflicts": "Key conflicts",
+  "layers.warnings": "Key warnings",
+  "layers.generator.keyboard": "Keyboard",
+  "layers.generator.hands": "Hands",
+  "layers.generator.hands.split": "Both hands",
+  "layers.generator.hands.left": "Left hand",
+  "layers.generator.hands.right": "Right hand",
+  "layers.generator.cellPx": "Target cell (px)",
+  "layers.generator.generate": "Generate layers",
+  "layers.precision.measure": "Measure precision",
+  "layers.precision.summary":
+    "Final cell {physical} physical px / {logical} logical px, {keystrokes} keystrokes",
+  "layers.precision.subPixel": "Below 1px",
+  "layers.precision.coarse": "Cells too large",
   "layers.addSingle": "Add single",
   "layers.addCombo": "Add combo",
   "layers.layerLabel": "Layer {index}",
   "layers.type.single": "Single",
   "layers.type.combo": "Combo",
+  "layers.type.bisect": "Bisect",
+  "layers.bisectHint":
+    "Each direction key keeps that half of the region; the finish key ends the layer.",
+  "layers.bisect.left": "Left half",
+  "layers.bisect.right": "Right half",
+  "layers.bisect.up": "Top half",
+  "layers.bisect.down": "Bottom half",
+  "layers.bisect.finish": "Finish",
+  "layers.type.points": "Points",
+  "layers.pointsHint":
+    "Each key clicks a fixed spot. Monitor-relative coordinates start at the monitor's top-left; set a monitor name to offer the point only there.",
+  "layers.point.key": "Key",
+  "layers.point.name": "Name",
+  "layers.point.x": "X (physical px)",
+  "layers.point.y": "Y (physical px)",
+  "layers.point.origin": "Origin",
+  "layers.point.monitor": "Monitor",
+  "layers.point.group": "Group",
+  "layers.pointOrigin.monitor": "Monitor-relative",
+  "layers.pointOrigin.absolute": "Absolute",
+  "layers.addPoint": "Add point",
+  "layers.removePoint": "Remove",
   "layers.mode": "Mode",
   "layers.moveUp": "Up",
   "layers.moveDown": "Down",
@@ -242,14 +423,25 @@ const en: Record<TranslationKey, string> = {
   "layers.keysHint": "Keys (space or comma separated)",
   "layers.autoFit": "Auto-fit",
   "layers.currentExpected": "Current: {current} / Expected: {expected}",
-  "layers.stage0": "Stage 0",
-  "layers.stage1": "Stage 1",
+  "layers.stage": "Stage {stage}",
+  "layers.addStage": "Add stage",
+  "layers.removeStage": "Remove stage",
+  "layers.rowWeights": "Row weights",
+  "layers.colWeights": "Column weights",
+  "layers.weightsPlaceholder": "Empty for equal bands, e.g. 1 1 3",
+  "layers.overlapRatio": "Cell overlap ratio",
   "hotkeys.section": "Hotkeys",
   "hotkeys.title": "Key Bindings",
   "hotkeys.subtitle": "Global shortcut syntax",
   "hotkeys.activation": "Activation",
   "hotkeys.controls": "Controls",
   "hotkeys.trigger": "Activate",
+  "hotkeys.local": "Activate Near Cursor",
+  "hotkeys.localPlaceholder": "Empty = off, e.g. Ctrl+'",
+  "hotkeys.window": "Activate on Focused Window",
+  "hotkeys.windowPlaceholder": "Empty = off, e.g. Ctrl+Shift+;",
+  "hotkeys.windowCustom":
+    "Window mode has {count} layers; edit window.layers in the config file.",
   "hotkeys.leftClick": "Left Click",
   "hotkeys.rightClick": "Right Click",
   "hotkeys.middleClick": "Middle Click",
@@ -258,10 +450,64 @@ const en: Record<TranslationKey, string> = {
   "hotkeys.cancel": "Cancel",
   "hotkeys.undo": "Undo",
   "hotkeys.directClick": "Direct Click",
+  "monitor.section": "Monitor",
+  "monitor.title": "Start Monitor",
+  "monitor.subtitle": "Which screen opens first",
+  "monitor.startStrategy": "Start strategy",
+  "monitor.strategy.primary": "Primary monitor",
+  "monitor.strategy.cursor": "Monitor under the cursor",
+  "monitor.strategy.focusedWindow": "Monitor with the focused window",
+  "monitor.strategy.lastUsed": "Last used monitor",
+  "monitor.strategy.named": "Monitor by name",
+  "monitor.startName": "Monitor name",
+  "monitor.previousKey": "Previous monitor",
+  "monitor.jumpKeys": "Jump keys",
+  "monitor.jumpPlaceholder": "In monitor order, e.g. F1 F2 F3",
+  "monitor.direction.left": "Monitor to the left",
+  "monitor.direction.right": "Monitor to the right",
+  "monitor.direction.up": "Monitor above",
+  "monitor.direction.down": "Monitor below",
+  "monitor.picker": "Pick a monitor on activation",
+  "monitor.spanAll": "First layer spans all monitors",
+  "monitor.showAll": "Show the grid on every monitor",
+  "monitor.useWorkArea": "Skip taskbars (use work area)",
+  "monitor.profiles":
+    "{count} monitor profiles override layers and overlay sizes; edit monitor.profiles in the config file.",
+  "monitor.detected": "{count} monitors connected",
+  "monitor.unnamed": "Unnamed",
+  "monitor.primary": "Primary",
+  "monitor.geometry": "{rect}, scale {scale}x",
+  "monitor.areas": "Work area {workArea}; overlay {usable}",
+  "monitor.margin.top": "Top margin (px)",
+  "monitor.margin.right": "Right margin (px)",
+  "monitor.margin.bottom": "Bottom margin (px)",
+  "monitor.margin.left": "Left margin (px)",
   "nudge.section": "Nudge",
   "nudge.title": "Nudge Settings",
   "nudge.subtitle": "Arrow step size",
   "nudge.step": "Step (px)",
+  "finish.section": "Finish",
+  "finish.title": "Auto-finish & Fallback",
+  "finish.subtitle": "End early or add a layer by region size",
+  "finish.autoBelowPx": "Auto-finish below (physical px, 0 = off)",
+  "finish.autoAction": "After auto-finish",
+  "finish.action.click": "Click the center",
+  "finish.action.nudge": "Nudge only, confirm with direct click",
+  "finish.fallbackAbovePx":
+    "Add the fallback layer above (physical px, 0 = off)",
+  "finish.fallbackKeys": "Fallback layer keys",
+  "finish.fallbackCustom":
+    "The fallback layer uses {mode} mode; edit it in the config file.",
+  "finish.anchorKeys": "Anchor keys (9 in keypad order, empty = off)",
+  "finish.anchorPlaceholder": "q w e a s d z x c",
+  "local.section": "Local",
+  "local.title": "Near-Cursor Mode",
+  "local.subtitle": "A small box around the mouse with its own layers",
+  "local.widthPx": "Box width (physical px)",
+  "local.heightPx": "Box height (physical px)",
+  "local.keys": "Local layer keys ({rows}x{cols})",
+  "local.custom":
+    "Local mode has {count} layers; edit local.layers in the config file.",
   "mouse.section": "Mouse",
   "mouse.title": "Mouse Behavior",
   "mouse.subtitle": "Path, randomness, landing",
@@ -292,11 +538,19 @@ const en: Record<TranslationKey, string> = {
   "overlay.fontSize": "Font Size",
   "overlay.showGrid": "Show Grid Lines",
   "overlay.showDiagonals": "Show Diagonals",
+  "overlay.scaleWithMonitor": "Scale text and lines per monitor",
+  "overlay.idleTimeout": "Close when idle (ms, 0 disables)",
+  "overlay.cancelOnFocusChange": "Close when another window takes focus",
   "overlay.maskColor": "Mask Color",
   "overlay.lineColor": "Line Color",
   "overlay.textColor": "Text Color",
   "overlay.fontFamily": "Font Family",
   "overlay.actionHint": "Action: {action} ({key} to cycle)",
+  "overlay.nudgeHint": "Arrows nudge, {key} clicks",
+  "overlay.anchorHint":
+    "Pick a corner, edge or center; {key} clicks the center",
+  "overlay.pickerHint": "Press a key to pick a monitor, {key} cancels",
+  "overlay.prefixHint": "Press {key} to continue on this monitor",
   "overlay.action.left": "Left Click",
   "overlay.action.right": "Right Click",
   "overlay.action.middle": "Middle Click",
@@ -361,14 +615,33 @@ const en: Record<TranslationKey, string> = {
   "errors.mouseMaxStepSleepMs": "Mouse maxStepSleepMs must be > 0.",
   "errors.overlayLineWidth": "Overlay lineWidthPx must be > 0.",
   "errors.overlayFontSize": "Overlay font sizePx must be > 0.",
+  "errors.reloadFailed":
+    "Override file reload failed; keeping current config: {message}",
   "errors.layerGridInvalidSimple": "Layer {index} grid is invalid.",
   "errors.layerExpectedKeysSimple": "Layer {index} expects {expected} keys.",
-  "errors.stage0GridInvalidSimple": "Layer {index} stage0 grid is invalid.",
-  "errors.stage1GridInvalidSimple": "Layer {index} stage1 grid is invalid.",
-  "errors.stage0ExpectedKeysSimple":
-    "Layer {index} stage0 expects {expected} keys.",
-  "errors.stage1ExpectedKeysSimple":
-    "Layer {index} stage1 expects {expected} keys.",
+  "errors.stageGridInvalidSimple":
+    "Layer {index} stage{stage} grid is invalid.",
+  "errors.stageExpectedKeysSimple":
+    "Layer {index} stage{stage} expects {expected} keys.",
+  "errors.stagesRequired": "Layer {index} needs at least one stage.",
+  "errors.fallbackLayerInvalid": "The fallback layer needs {expected} keys.",
+  "errors.anchorKeysInvalid": "Anchor keys need 9 non-empty keys.",
+  "errors.startNameEmpty": "Enter a monitor name for the named start strategy.",
+  "errors.pickerNeedsJumpKeys": "The monitor picker needs jump keys.",
+  "errors.showAllNeedsJumpKeys": "Showing every monitor needs jump keys.",
+  "errors.localInvalid":
+    "Near-cursor mode needs a non-empty box and one key per grid cell.",
+  "errors.overlapRatioInvalid":
+    "Layer {index} overlap ratio must be between 0 and {max}.",
+  "errors.layerWeightsInvalid":
+    "Layer {index} weights must match its rows/columns and be > 0.",
+  "errors.stageWeightsInvalid":
+    "Layer {index} stage {stage} weights must match its rows/columns and be > 0.",
+  "errors.bisectKeysEmpty": "Layer {index} bisect keys must not be empty.",
+  "errors.pointsRequired": "Layer {index} needs at least one point.",
+  "errors.pointKeyEmpty": "Layer {index} point {point} has no key.",
+  "errors.pointPositionInvalid":
+    "Layer {index} point {point} has an invalid position.",
 };
 
 const translations: Record<Locale, Record<TranslationKey, string>> = {
@@ -1,17 +1,136 @@
-﻿import type { AppConfig, Region } from "$lib/core";
+﻿import type {
+  AppConfig,
+  ClickAnchor,
+  Layer,
+  MonitorArea,
+  Region,
+} from "$lib/core";
 
 export type ClickAction = "left" | "right" | "middle" | "moveOnly" | "drag";
 
+export type ConfigFormat = "json" | "toml" | "yaml";
+
+export type ImportMode = "replace" | "merge" | "patch";
+
+export interface ConfigChange {
+  path: string;
+  before?: unknown;
+  after?: unknown;
+}
+
+export type KeyIssueCode =
+  | "duplicateKey"
+  | "controlKeyConflict"
+  | "nudgeKeyConflict"
+  | "duplicateControlKey"
+  | "stageOverlap"
+  | "unbindableKey";
+
+export interface KeyIssue {
+  code: KeyIssueCode;
+  message: string;
+  key: string;
+  layer?: number;
+  stage?: number;
+}
+
+export interface KeyConflictReport {
+  errors: KeyIssue[];
+  warnings: KeyIssue[];
+}
+
+export type KeyboardLayout = "qwerty" | "dvorak" | "colemak" | "azerty";
+
+export type HandPreference = "split" | "left" | "right";
+
+export interface LayoutRequest {
+  keyboard: KeyboardLayout;
+  hands: HandPreference;
+  monitorWidth: number;
+  monitorHeight: number;
+  targetCellPx: number;
+}
+
+export interface GeneratedLayout {
+  layers: Layer[];
+  cellWidth: number;
+  cellHeight: number;
+}
+
+export type PrecisionFlag = "subPixel" | "coarse";
+
+export interface LayerPrecision {
+  layer: number;
+  keystrokes: number;
+  cellWidth: number;
+  cellHeight: number;
+  flags: PrecisionFlag[];
+}
+
+export interface MonitorPrecision {
+  index: number;
+  name: string | null;
+  width: number;
+  height: number;
+  scaleFactor: number;
+  layers: LayerPrecision[];
+  finalCellWidth: number;
+  finalCellHeight: number;
+  finalLogicalWidth: number;
+  finalLogicalHeight: number;
+  keystrokes: number;
+}
+
+export interface MonitorSummary {
+  index: number;
+  name: string | null;
+  // 位置与物理像素尺寸
+  full: Region;
+  workArea: Region;
+  // 按当前配置覆盖层实际覆盖的区域
+  usable: Region;
+  scaleFactor: number;
+  primary: boolean;
+}
+
+export interface PickerMonitor {
+  key: string;
+  name: string | null;
+  region: Region;
+}
+
 export interface OverlayActivatePayload {
   region: Region;
+  monitor: string | null;
   config: AppConfig;
   clickAction: ClickAction;
+  // 非空时先选择显示器，region 为整个虚拟桌面
+  picker: PickerMonitor[];
+  // 跨屏模式下的各显示器，region 为它们的外接矩形
+  areas: MonitorArea[];
+  // 同时显示所有显示器时本屏的 jumpKeys 按键
+  prefix: string | null;
 }
 
 export interface NativeKeyPayload {
   key: string;
 }
 
+// 与后端 CloseReason 对应
+export type CloseReason =
+  | "click"
+  | "cancel"
+  | "error"
+  | "idleTimeout"
+  | "focusChanged"
+  | "monitorsChanged"
+  | "configChanged"
+  | "paused";
+
+export interface OverlayClosedPayload {
+  reason: CloseReason;
+}
+
 export interface OverlayActionPayload {
   clickAction: ClickAction;
 }
@@ -20,4 +139,9 @@ export interface NativeClickPayload {
   x: number;
   y: number;
   button: ClickAction;
+  anchor?: ClickAnchor;
+}
+
+export interface ConfigReloadErrorPayload {
+  message: string;
 }